@@ -27,9 +27,7 @@
 //! ```
 
 use crate::{
-    beacon_chain::{
-        HEAD_LOCK_TIMEOUT, MAXIMUM_GOSSIP_CLOCK_DISPARITY, VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT,
-    },
+    beacon_chain::{HEAD_LOCK_TIMEOUT, VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT},
     metrics,
     observed_attestations::ObserveOutcome,
     observed_attesters::Error as ObservedAttestersError,
@@ -524,6 +522,16 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
 
         let indexed_attestation =
             match map_attestation_committee(chain, attestation, |(committee, _)| {
+                // The following checks are cheap and must be performed before any signature
+                // verification. This avoids the expense of verifying the aggregate signature for
+                // messages from validators which are not aggregators.
+
+                // Ensure the aggregator is a member of the committee for which it is aggregating.
+                if !committee.committee.contains(&(aggregator_index as usize)) {
+                    metrics::inc_counter(&metrics::AGGREGATED_ATTESTATION_NON_AGGREGATOR_REJECTS);
+                    return Err(Error::AggregatorNotInCommittee { aggregator_index });
+                }
+
                 // Note: this clones the signature which is known to be a relatively slow operation.
                 //
                 // Future optimizations should remove this clone.
@@ -534,14 +542,10 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
                     .is_aggregator(committee.committee.len(), &chain.spec)
                     .map_err(|e| Error::BeaconChainError(e.into()))?
                 {
+                    metrics::inc_counter(&metrics::AGGREGATED_ATTESTATION_NON_AGGREGATOR_REJECTS);
                     return Err(Error::InvalidSelectionProof { aggregator_index });
                 }
 
-                // Ensure the aggregator is a member of the committee for which it is aggregating.
-                if !committee.committee.contains(&(aggregator_index as usize)) {
-                    return Err(Error::AggregatorNotInCommittee { aggregator_index });
-                }
-
                 get_indexed_attestation(committee.committee, attestation)
                     .map_err(|e| BeaconChainError::from(e).into())
             }) {
@@ -844,16 +848,18 @@ fn verify_head_block_is_known<T: BeaconChainTypes>(
 /// Verify that the `attestation` is within the acceptable gossip propagation range, with reference
 /// to the current slot of the `chain`.
 ///
-/// Accounts for `MAXIMUM_GOSSIP_CLOCK_DISPARITY`.
+/// Accounts for `BeaconChain::maximum_gossip_clock_disparity` and limits the range to
+/// `BeaconChain::attestation_propagation_slot_range`.
 pub fn verify_propagation_slot_range<T: BeaconChainTypes>(
     chain: &BeaconChain<T>,
     attestation: &Attestation<T::EthSpec>,
 ) -> Result<(), Error> {
     let attestation_slot = attestation.data.slot;
+    let clock_disparity = chain.maximum_gossip_clock_disparity();
 
     let latest_permissible_slot = chain
         .slot_clock
-        .now_with_future_tolerance(MAXIMUM_GOSSIP_CLOCK_DISPARITY)
+        .now_with_future_tolerance(clock_disparity)
         .ok_or(BeaconChainError::UnableToReadSlot)?;
     if attestation_slot > latest_permissible_slot {
         return Err(Error::FutureSlot {
@@ -865,9 +871,9 @@ pub fn verify_propagation_slot_range<T: BeaconChainTypes>(
     // Taking advantage of saturating subtraction on `Slot`.
     let earliest_permissible_slot = chain
         .slot_clock
-        .now_with_past_tolerance(MAXIMUM_GOSSIP_CLOCK_DISPARITY)
+        .now_with_past_tolerance(clock_disparity)
         .ok_or(BeaconChainError::UnableToReadSlot)?
-        - T::EthSpec::slots_per_epoch();
+        - chain.attestation_propagation_slot_range();
     if attestation_slot < earliest_permissible_slot {
         return Err(Error::PastSlot {
             attestation_slot,
@@ -3,12 +3,14 @@ use crate::attestation_verification::{
     VerifiedUnaggregatedAttestation,
 };
 use crate::beacon_proposer_cache::BeaconProposerCache;
+use crate::block_times_cache::BlockTimesCache;
 use crate::block_verification::{
     check_block_is_finalized_descendant, check_block_relevancy, get_block_root,
     signature_verify_chain_segment, BlockError, FullyVerifiedBlock, GossipVerifiedBlock,
     IntoFullyVerifiedBlock,
 };
 use crate::chain_config::ChainConfig;
+use crate::disk_space_monitor::DiskSpace;
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
 use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
 use crate::events::ServerSentEventHandler;
@@ -21,6 +23,10 @@ use crate::observed_block_producers::ObservedBlockProducers;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
+use crate::persisted_metrics::PersistedMetrics;
+use crate::persisted_validator_monitor::{
+    PersistedValidatorMonitor, PersistedValidatorMonitorSummaries,
+};
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::snapshot_cache::SnapshotCache;
 use crate::timeout_rw_lock::TimeoutRwLock;
@@ -32,7 +38,7 @@ use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::BeaconForkChoiceStore;
 use crate::BeaconSnapshot;
 use crate::{metrics, BeaconChainError};
-use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+use eth2::types::{EventKind, SseBlock, SseDependentRoot, SseFinalizedCheckpoint, SseHead};
 use fork_choice::ForkChoice;
 use futures::channel::mpsc::Sender;
 use itertools::process_results;
@@ -85,6 +91,9 @@ pub const BEACON_CHAIN_DB_KEY: Hash256 = Hash256::zero();
 pub const OP_POOL_DB_KEY: Hash256 = Hash256::zero();
 pub const ETH1_CACHE_DB_KEY: Hash256 = Hash256::zero();
 pub const FORK_CHOICE_DB_KEY: Hash256 = Hash256::zero();
+pub const VALIDATOR_MONITOR_DB_KEY: Hash256 = Hash256::zero();
+pub const METRICS_DB_KEY: Hash256 = Hash256::zero();
+pub const VALIDATOR_MONITOR_SUMMARIES_DB_KEY: Hash256 = Hash256::zero();
 
 /// Defines the behaviour when a block/block-root for a skipped slot is requested.
 pub enum WhenSlotSkipped {
@@ -110,9 +119,11 @@ pub enum ChainSegmentResult<T: EthSpec> {
     },
 }
 
-/// The accepted clock drift for nodes gossiping blocks and attestations. See:
+/// The default accepted clock drift for nodes gossiping blocks and attestations. See:
 ///
 /// https://github.com/ethereum/eth2.0-specs/blob/v0.12.1/specs/phase0/p2p-interface.md#configuration
+///
+/// The value in use is given by `BeaconChain::maximum_gossip_clock_disparity`.
 pub const MAXIMUM_GOSSIP_CLOCK_DISPARITY: Duration = Duration::from_millis(500);
 
 #[derive(Debug, PartialEq)]
@@ -277,6 +288,10 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub slasher: Option<Arc<Slasher<T::EthSpec>>>,
     /// Provides monitoring of a set of explicitly defined validators.
     pub validator_monitor: RwLock<ValidatorMonitor<T::EthSpec>>,
+    /// The free disk space, updated by the disk space monitor.
+    pub disk_space: DiskSpace,
+    /// Records the time at which recent blocks were imported.
+    pub block_times_cache: RwLock<BlockTimesCache>,
 }
 
 type BeaconBlockAndState<T> = (BeaconBlock<T>, BeaconState<T>);
@@ -371,6 +386,78 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persists the current values of selected metrics counters to disk, if enabled via
+    /// `ChainConfig::persist_metrics`.
+    pub fn persist_metrics(&self) -> Result<(), Error> {
+        if self.config.persist_metrics {
+            self.store
+                .put_item(&METRICS_DB_KEY, &PersistedMetrics::from_current_values())?;
+        }
+
+        Ok(())
+    }
+
+    /// Persists the recent epoch summaries of the validators in `self.validator_monitor` to disk,
+    /// so the per-epoch metrics are not reset by a restart.
+    ///
+    /// The summaries are written even if there are none, so that stale summaries are not restored.
+    pub fn persist_validator_monitor_summaries(&self) -> Result<(), Error> {
+        let current_epoch = self
+            .slot_clock
+            .now_or_genesis()
+            .ok_or(Error::UnableToReadSlot)?
+            .epoch(T::EthSpec::slots_per_epoch());
+        let persisted = self
+            .validator_monitor
+            .read()
+            .to_persisted_summaries(current_epoch);
+        self.store
+            .put_item(&VALIDATOR_MONITOR_SUMMARIES_DB_KEY, &persisted)?;
+
+        Ok(())
+    }
+
+    /// Start monitoring `pubkeys` with `self.validator_monitor`, persisting them to disk so they
+    /// are still monitored after a restart.
+    pub fn register_monitored_validators(&self, pubkeys: &[PublicKeyBytes]) -> Result<(), Error> {
+        let mut validator_monitor = self.validator_monitor.write();
+        let mut persisted = self
+            .store
+            .get_item::<PersistedValidatorMonitor>(&VALIDATOR_MONITOR_DB_KEY)?
+            .unwrap_or_default();
+
+        for pubkey in pubkeys {
+            validator_monitor.add_validator_pubkey(*pubkey);
+            if !persisted.pubkeys.contains(pubkey) {
+                persisted.pubkeys.push(*pubkey);
+            }
+        }
+
+        self.store.put_item(&VALIDATOR_MONITOR_DB_KEY, &persisted)?;
+
+        Ok(())
+    }
+
+    /// Stop monitoring `pubkeys` with `self.validator_monitor` and remove them from disk.
+    ///
+    /// Validators supplied via the CLI will be monitored again after a restart.
+    pub fn unregister_monitored_validators(&self, pubkeys: &[PublicKeyBytes]) -> Result<(), Error> {
+        let mut validator_monitor = self.validator_monitor.write();
+        let mut persisted = self
+            .store
+            .get_item::<PersistedValidatorMonitor>(&VALIDATOR_MONITOR_DB_KEY)?
+            .unwrap_or_default();
+
+        for pubkey in pubkeys {
+            validator_monitor.remove_validator_pubkey(pubkey);
+        }
+        persisted.pubkeys.retain(|pubkey| !pubkeys.contains(pubkey));
+
+        self.store.put_item(&VALIDATOR_MONITOR_DB_KEY, &persisted)?;
+
+        Ok(())
+    }
+
     /// Returns the slot _right now_ according to `self.slot_clock`. Returns `Err` if the slot is
     /// unavailable.
     ///
@@ -390,6 +477,31 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .map(|slot| slot.epoch(T::EthSpec::slots_per_epoch()))
     }
 
+    /// Returns the accepted clock drift for nodes gossiping blocks and attestations.
+    ///
+    /// Uses the `ChainConfig` override if one is set, otherwise the value from the `ChainSpec`.
+    pub fn maximum_gossip_clock_disparity(&self) -> Duration {
+        self.config
+            .maximum_gossip_clock_disparity_millis
+            .map(Duration::from_millis)
+            .unwrap_or_else(|| self.spec.maximum_gossip_clock_disparity())
+    }
+
+    /// Returns the number of slots after `attestation.data.slot` for which an attestation will be
+    /// accepted on gossip.
+    ///
+    /// Uses the `ChainConfig` override if one is set, otherwise the value from the `ChainSpec`.
+    /// The range is limited to `SLOTS_PER_EPOCH`, since the observed attestation caches do not
+    /// retain attestations for any longer.
+    pub fn attestation_propagation_slot_range(&self) -> u64 {
+        std::cmp::min(
+            self.config
+                .attestation_propagation_slot_range
+                .unwrap_or(self.spec.attestation_propagation_slot_range),
+            T::EthSpec::slots_per_epoch(),
+        )
+    }
+
     /// Iterates across all `(block_root, slot)` pairs from the head of the chain (inclusive) to
     /// the earliest reachable ancestor (may or may not be genesis).
     ///
@@ -1923,10 +2035,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         //
         // We're declaring the block "imported" at this point, since fork choice and the DB know
         // about it.
+        let block_delay =
+            get_block_delay_ms(timestamp_now(), &signed_block.message, &self.slot_clock);
         metrics::observe_duration(
             &metrics::BEACON_BLOCK_IMPORTED_SLOT_START_DELAY_TIME,
-            get_block_delay_ms(timestamp_now(), &signed_block.message, &self.slot_clock),
+            block_delay,
         );
+        self.block_times_cache
+            .write()
+            .set_time_imported(block_root, block.slot, block_delay);
 
         let parent_root = block.parent_root;
         let slot = block.slot;
@@ -2141,10 +2258,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let attestations = self
             .op_pool
-            .get_attestations(
+            .get_attestations_with_strategy(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                self.config.attestation_packing_strategy,
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?
@@ -2320,7 +2438,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         if is_epoch_transition || is_reorg {
             self.persist_head_and_fork_choice()?;
             self.op_pool.prune_attestations(self.epoch()?);
-            self.persist_op_pool()?;
+            // Skip non-critical writes whilst disk space is low.
+            if !self.disk_space.is_protective_mode() {
+                self.persist_op_pool()?;
+                self.persist_metrics()?;
+                self.persist_validator_monitor_summaries()?;
+            }
         }
 
         let update_head_timer = metrics::start_timer(&metrics::UPDATE_HEAD_TIMES);
@@ -2337,6 +2460,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .previous_epoch()
             .start_slot(T::EthSpec::slots_per_epoch());
 
+        // If the head is being re-orged, record the dependent roots of the old chain so they can be
+        // compared to those of the new chain.
+        let prior_dependent_roots = if is_reorg
+            && self.event_handler.as_ref().map_or(false, |event_handler| {
+                event_handler.has_dependent_root_subscribers()
+            }) {
+            self.duty_dependent_roots(target_epoch_start_slot, prev_target_epoch_start_slot)
+                .unwrap_or(None)
+        } else {
+            None
+        };
+
         // Update the snapshot that stores the head of the chain at the time it received the
         // block.
         *self
@@ -2359,7 +2494,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         //
         // Don't create this log if the block was > 4 slots old, this helps prevent noise during
         // sync.
-        if block_delay >= self.slot_clock.unagg_attestation_production_delay()
+        if block_delay >= self.spec.unagg_attestation_production_delay()
             && block_delay < self.slot_clock.slot_duration() * 4
         {
             metrics::inc_counter(&metrics::BEACON_BLOCK_HEAD_SLOT_START_DELAY_EXCEEDED_TOTAL);
@@ -2452,11 +2587,52 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     );
                 }
             }
+
+            // Notify subscribers if the re-org changed the dependent roots, since any duties
+            // computed from the old chain may now be invalid.
+            if let Some(prior_dependent_roots) = prior_dependent_roots {
+                match self
+                    .duty_dependent_roots(target_epoch_start_slot, prev_target_epoch_start_slot)
+                {
+                    Ok(Some(dependent_roots)) if dependent_roots != prior_dependent_roots => {
+                        let (current_duty_dependent_root, previous_duty_dependent_root) =
+                            dependent_roots;
+                        event_handler.register(EventKind::DependentRoot(SseDependentRoot {
+                            slot: head_slot,
+                            block: beacon_block_root,
+                            current_duty_dependent_root,
+                            previous_duty_dependent_root,
+                        }));
+                    }
+                    Ok(Some(_)) => (),
+                    Ok(None) | Err(_) => warn!(
+                        self.log,
+                        "Unable to find dependent roots, cannot register dependent root event"
+                    ),
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Returns the `(current_duty_dependent_root, previous_duty_dependent_root)` of the canonical
+    /// chain, where the given slots are the first slots of the current and previous epochs.
+    ///
+    /// Returns `Ok(None)` if either root is unknown.
+    fn duty_dependent_roots(
+        &self,
+        current_epoch_start_slot: Slot,
+        previous_epoch_start_slot: Slot,
+    ) -> Result<Option<(Hash256, Hash256)>, Error> {
+        let current_duty_dependent_root =
+            self.block_root_at_slot(current_epoch_start_slot - 1, WhenSlotSkipped::Prev)?;
+        let previous_duty_dependent_root =
+            self.block_root_at_slot(previous_epoch_start_slot - 1, WhenSlotSkipped::Prev)?;
+
+        Ok(current_duty_dependent_root.zip(previous_duty_dependent_root))
+    }
+
     /// This function takes a configured weak subjectivity `Checkpoint` and the latest finalized `Checkpoint`.
     /// If the weak subjectivity checkpoint and finalized checkpoint share the same epoch, we compare
     /// roots. If we the weak subjectivity checkpoint is from an older epoch, we iterate back through
@@ -2933,7 +3109,13 @@ impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
     fn drop(&mut self) {
         let drop = || -> Result<(), Error> {
             self.persist_head_and_fork_choice()?;
+            // Skip non-critical writes whilst disk space is low.
+            if self.disk_space.is_protective_mode() {
+                return Ok(());
+            }
             self.persist_op_pool()?;
+            self.persist_metrics()?;
+            self.persist_validator_monitor_summaries()?;
             self.persist_eth1_cache()
         };
 
@@ -0,0 +1,64 @@
+//! Records the time at which recent blocks were imported, relative to the start of their slot.
+//!
+//! This is used by the HTTP API to report on the timeliness of recent blocks. Only blocks from the
+//! last `BLOCK_TIMES_CACHE_SLOTS` slots are retained.
+use std::collections::HashMap;
+use std::time::Duration;
+use types::{Hash256, Slot};
+
+/// The number of slots for which block times are retained.
+pub const BLOCK_TIMES_CACHE_SLOTS: u64 = 64;
+
+#[derive(Debug, Default)]
+pub struct BlockTimesCache {
+    /// Maps a block root to the slot of the block and its import delay.
+    cache: HashMap<Hash256, (Slot, Duration)>,
+}
+
+impl BlockTimesCache {
+    /// Records that the block with `block_root` at `slot` was imported `delay` after the start of
+    /// `slot`, pruning any blocks which are more than `BLOCK_TIMES_CACHE_SLOTS` older than `slot`.
+    pub fn set_time_imported(&mut self, block_root: Hash256, slot: Slot, delay: Duration) {
+        self.cache.insert(block_root, (slot, delay));
+        self.prune(slot);
+    }
+
+    /// Returns the delay between the start of the block's slot and when it was imported, if known.
+    pub fn get_import_delay(&self, block_root: &Hash256) -> Option<Duration> {
+        self.cache.get(block_root).map(|(_, delay)| *delay)
+    }
+
+    fn prune(&mut self, current_slot: Slot) {
+        let min_slot = current_slot.saturating_sub(BLOCK_TIMES_CACHE_SLOTS);
+        self.cache.retain(|_, (slot, _)| *slot >= min_slot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prunes_old_blocks() {
+        let mut cache = BlockTimesCache::default();
+        let old_root = Hash256::repeat_byte(1);
+        let new_root = Hash256::repeat_byte(2);
+
+        cache.set_time_imported(old_root, Slot::new(1), Duration::from_millis(100));
+        assert_eq!(
+            cache.get_import_delay(&old_root),
+            Some(Duration::from_millis(100))
+        );
+
+        cache.set_time_imported(
+            new_root,
+            Slot::new(2 + BLOCK_TIMES_CACHE_SLOTS),
+            Duration::from_millis(200),
+        );
+        assert_eq!(cache.get_import_delay(&old_root), None);
+        assert_eq!(
+            cache.get_import_delay(&new_root),
+            Some(Duration::from_millis(200))
+        );
+    }
+}
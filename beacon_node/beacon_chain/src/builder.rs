@@ -1,8 +1,16 @@
-use crate::beacon_chain::{BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY};
+use crate::beacon_chain::{
+    BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, METRICS_DB_KEY, OP_POOL_DB_KEY,
+    VALIDATOR_MONITOR_DB_KEY, VALIDATOR_MONITOR_SUMMARIES_DB_KEY,
+};
+use crate::disk_space_monitor::DiskSpace;
 use crate::eth1_chain::{CachingEth1Backend, SszEth1};
 use crate::head_tracker::HeadTracker;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
+use crate::persisted_metrics::PersistedMetrics;
+use crate::persisted_validator_monitor::{
+    PersistedValidatorMonitor, PersistedValidatorMonitorSummaries,
+};
 use crate::shuffling_cache::ShufflingCache;
 use crate::snapshot_cache::{SnapshotCache, DEFAULT_SNAPSHOT_CACHE_SIZE};
 use crate::timeout_rw_lock::TimeoutRwLock;
@@ -385,6 +393,7 @@ where
         self.validator_monitor = Some(ValidatorMonitor::new(
             validators,
             auto_register,
+            &self.spec,
             log.clone(),
         ));
         self
@@ -489,6 +498,36 @@ where
             log.clone(),
         );
 
+        // Resume monitoring any validators that were registered via the HTTP API.
+        if let Some(persisted) = store
+            .get_item::<PersistedValidatorMonitor>(&VALIDATOR_MONITOR_DB_KEY)
+            .map_err(|e| format!("DB error reading persisted validator monitor: {:?}", e))?
+        {
+            for pubkey in persisted.pubkeys {
+                validator_monitor.add_validator_pubkey(pubkey);
+            }
+        }
+
+        // Restore the recent epoch summaries of monitored validators, including those which are
+        // only registered after start-up.
+        if let Some(persisted) = store
+            .get_item::<PersistedValidatorMonitorSummaries>(&VALIDATOR_MONITOR_SUMMARIES_DB_KEY)
+            .map_err(|e| format!("DB error reading validator monitor summaries: {:?}", e))?
+        {
+            validator_monitor
+                .restore_summaries(persisted, current_slot.epoch(TEthSpec::slots_per_epoch()));
+        }
+
+        // Resume counting from the values of any metrics persisted prior to shutdown.
+        if self.chain_config.persist_metrics {
+            if let Some(persisted) = store
+                .get_item::<PersistedMetrics>(&METRICS_DB_KEY)
+                .map_err(|e| format!("DB error reading persisted metrics: {:?}", e))?
+            {
+                persisted.restore();
+            }
+        }
+
         if let Some(slot) = slot_clock.now() {
             validator_monitor.process_valid_state(
                 slot.epoch(TEthSpec::slots_per_epoch()),
@@ -540,6 +579,8 @@ where
             graffiti: self.graffiti,
             slasher: self.slasher.clone(),
             validator_monitor: RwLock::new(validator_monitor),
+            disk_space: DiskSpace::new(),
+            block_times_cache: <_>::default(),
         };
 
         let head = beacon_chain
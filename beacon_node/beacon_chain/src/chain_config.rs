@@ -1,3 +1,4 @@
+use operation_pool::AttestationPackingStrategy;
 use serde_derive::{Deserialize, Serialize};
 use types::Checkpoint;
 
@@ -12,6 +13,19 @@ pub struct ChainConfig {
     ///
     /// If `None`, there is no weak subjectivity verification.
     pub weak_subjectivity_checkpoint: Option<Checkpoint>,
+    /// Persist selected metrics counters to the database so they are retained across restarts.
+    pub persist_metrics: bool,
+    /// Enter a protective mode which disables non-critical database writes when the free disk
+    /// space (in bytes) drops below this value.
+    ///
+    /// If `None`, disk space is not monitored.
+    pub disk_space_threshold: Option<u64>,
+    /// The algorithm used to select attestations from the operation pool during block production.
+    pub attestation_packing_strategy: AttestationPackingStrategy,
+    /// Overrides `ChainSpec::maximum_gossip_clock_disparity_millis`.
+    pub maximum_gossip_clock_disparity_millis: Option<u64>,
+    /// Overrides `ChainSpec::attestation_propagation_slot_range`.
+    pub attestation_propagation_slot_range: Option<u64>,
 }
 
 impl Default for ChainConfig {
@@ -19,6 +33,11 @@ impl Default for ChainConfig {
         Self {
             import_max_skip_slots: None,
             weak_subjectivity_checkpoint: None,
+            persist_metrics: false,
+            disk_space_threshold: None,
+            attestation_packing_strategy: AttestationPackingStrategy::default(),
+            maximum_gossip_clock_disparity_millis: None,
+            attestation_propagation_slot_range: None,
         }
     }
 }
@@ -0,0 +1,143 @@
+//! Periodically checks the free space on the disk(s) containing the database and switches the
+//! `BeaconChain` into a "protective mode" when it drops below
+//! `ChainConfig::disk_space_threshold`.
+//!
+//! Whilst in protective mode, non-critical database writes (e.g., persisting the operation pool)
+//! are skipped so the remaining space is reserved for importing blocks. Running out of space
+//! entirely may corrupt the database, so the node also raises a critical log and sets the
+//! `beacon_disk_space_protective_mode` metric to alert the operator.
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use slog::{crit, info, warn, Logger};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::time::interval;
+
+/// The interval between checks of the free disk space.
+const DISK_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Bytes per megabyte, used for logging.
+const MEGABYTE: u64 = 1 << 20;
+
+/// Tracks the most recent free disk space reading and whether the node is in protective mode.
+#[derive(Debug)]
+pub struct DiskSpace {
+    protective_mode: AtomicBool,
+    /// The free space in bytes, or `u64::max_value()` if it has not been read.
+    free_bytes: AtomicU64,
+}
+
+impl Default for DiskSpace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiskSpace {
+    pub fn new() -> Self {
+        Self {
+            protective_mode: AtomicBool::new(false),
+            free_bytes: AtomicU64::new(u64::max_value()),
+        }
+    }
+
+    /// Returns `true` if free disk space has dropped below the configured threshold.
+    pub fn is_protective_mode(&self) -> bool {
+        self.protective_mode.load(Ordering::Relaxed)
+    }
+
+    /// Returns the free space in bytes as of the most recent check, if any.
+    pub fn free_bytes(&self) -> Option<u64> {
+        Some(self.free_bytes.load(Ordering::Relaxed)).filter(|free| *free != u64::max_value())
+    }
+
+    /// Records a new reading, returning `true` if this changed whether or not the node is in
+    /// protective mode.
+    fn update(&self, free_bytes: u64, threshold: u64) -> bool {
+        self.free_bytes.store(free_bytes, Ordering::Relaxed);
+        let protective_mode = free_bytes < threshold;
+        self.protective_mode
+            .swap(protective_mode, Ordering::Relaxed)
+            != protective_mode
+    }
+}
+
+/// Spawns the monitor described in the module-level documentation, checking the free space of
+/// the disk(s) containing each of `paths`.
+///
+/// Does nothing if `ChainConfig::disk_space_threshold` is `None`.
+pub fn spawn_disk_space_monitor<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    paths: Vec<PathBuf>,
+    log: Logger,
+) {
+    if let Some(threshold) = beacon_chain.config.disk_space_threshold {
+        executor.spawn(
+            disk_space_monitor(beacon_chain, paths, threshold, log),
+            "disk_space_monitor",
+        );
+    }
+}
+
+async fn disk_space_monitor<T: BeaconChainTypes>(
+    beacon_chain: Arc<BeaconChain<T>>,
+    paths: Vec<PathBuf>,
+    threshold: u64,
+    log: Logger,
+) {
+    let mut interval = interval(DISK_SPACE_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        // Use the disk with the least free space if the databases are on separate disks.
+        let mut free_bytes = None;
+        for path in &paths {
+            match fs2::available_space(path) {
+                Ok(free) => free_bytes = Some(free_bytes.map_or(free, |min: u64| min.min(free))),
+                Err(e) => warn!(
+                    log,
+                    "Unable to read free disk space";
+                    "path" => ?path,
+                    "error" => %e,
+                ),
+            }
+        }
+        let free_bytes = if let Some(free_bytes) = free_bytes {
+            free_bytes
+        } else {
+            continue;
+        };
+
+        metrics::set_gauge(&metrics::DISK_SPACE_FREE_BYTES, free_bytes as i64);
+
+        let disk_space = &beacon_chain.disk_space;
+        if disk_space.update(free_bytes, threshold) {
+            if disk_space.is_protective_mode() {
+                crit!(
+                    log,
+                    "Low disk space, entering protective mode";
+                    "msg" => "non-critical database writes are disabled, free up disk space \
+                              to avoid database corruption",
+                    "free_mb" => free_bytes / MEGABYTE,
+                    "threshold_mb" => threshold / MEGABYTE,
+                );
+            } else {
+                info!(
+                    log,
+                    "Disk space recovered, leaving protective mode";
+                    "free_mb" => free_bytes / MEGABYTE,
+                    "threshold_mb" => threshold / MEGABYTE,
+                );
+            }
+        }
+
+        metrics::set_gauge(
+            &metrics::DISK_SPACE_PROTECTIVE_MODE,
+            disk_space.is_protective_mode() as i64,
+        );
+    }
+}
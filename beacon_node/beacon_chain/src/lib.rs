@@ -4,9 +4,11 @@ mod beacon_chain;
 mod beacon_fork_choice_store;
 mod beacon_proposer_cache;
 mod beacon_snapshot;
+pub mod block_times_cache;
 mod block_verification;
 pub mod builder;
 pub mod chain_config;
+pub mod disk_space_monitor;
 mod errors;
 pub mod eth1_chain;
 pub mod events;
@@ -20,6 +22,8 @@ mod observed_block_producers;
 pub mod observed_operations;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
+mod persisted_metrics;
+mod persisted_validator_monitor;
 pub mod schema_change;
 mod shuffling_cache;
 mod snapshot_cache;
@@ -42,6 +46,7 @@ pub use block_verification::{BlockError, GossipVerifiedBlock};
 pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::ServerSentEventHandler;
 pub use metrics::scrape_for_metrics;
+pub use operation_pool::AttestationPackingStrategy;
 pub use parking_lot;
 pub use slot_clock;
 pub use state_processing::per_block_processing::errors::{
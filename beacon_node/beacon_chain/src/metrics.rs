@@ -128,6 +128,11 @@ lazy_static! {
         "beacon_aggregated_attestation_processing_successes_total",
         "Number of aggregated attestations verified for gossip"
     );
+    pub static ref AGGREGATED_ATTESTATION_NON_AGGREGATOR_REJECTS: Result<IntCounter> = try_create_int_counter(
+        "beacon_aggregated_attestation_non_aggregator_rejects_total",
+        "Number of aggregated attestations rejected before signature verification because the \
+        sender is not an aggregator"
+    );
     pub static ref AGGREGATED_ATTESTATION_GOSSIP_VERIFICATION_TIMES: Result<Histogram> = try_create_histogram(
         "beacon_aggregated_attestation_gossip_verification_seconds",
         "Full runtime of aggregated attestation gossip verification"
@@ -272,6 +277,14 @@ lazy_static! {
     pub static ref PERSIST_FORK_CHOICE: Result<Histogram> =
         try_create_histogram("beacon_persist_fork_choice", "Time taken to persist the fork choice struct");
 
+    /*
+     * Disk space
+     */
+    pub static ref DISK_SPACE_FREE_BYTES: Result<IntGauge> =
+        try_create_int_gauge("beacon_disk_space_free_bytes", "Free space on the disk containing the database");
+    pub static ref DISK_SPACE_PROTECTIVE_MODE: Result<IntGauge> =
+        try_create_int_gauge("beacon_disk_space_protective_mode", "Set to 1 when non-critical writes are disabled due to low disk space");
+
     /*
      * Eth1
      */
@@ -0,0 +1,202 @@
+//! Allows selected monotonic counters to be persisted to the database and restored after a restart,
+//! so that long-running dashboards are not disrupted by the counters resetting to zero.
+//!
+//! Persistence is enabled via `ChainConfig::persist_metrics`.
+use crate::metrics::{self, Collector, IntCounter, IntCounterVec, Result as MetricsResult};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error, StoreItem};
+
+/// A counter which is persisted across restarts.
+enum PersistedCollector {
+    Counter(&'static MetricsResult<IntCounter>),
+    CounterVec(&'static MetricsResult<IntCounterVec>),
+}
+
+/// The counters which are persisted across restarts.
+fn persisted_collectors() -> Vec<PersistedCollector> {
+    vec![
+        PersistedCollector::Counter(&metrics::FORK_CHOICE_REORG_COUNT),
+        PersistedCollector::Counter(&metrics::BLOCK_PRODUCTION_REQUESTS),
+        PersistedCollector::Counter(&metrics::BLOCK_PRODUCTION_SUCCESSES),
+        PersistedCollector::CounterVec(
+            &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_ATTESTER_MISS,
+        ),
+        PersistedCollector::CounterVec(
+            &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_HEAD_ATTESTER_MISS,
+        ),
+        PersistedCollector::CounterVec(
+            &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_TARGET_ATTESTER_MISS,
+        ),
+        PersistedCollector::CounterVec(&metrics::VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL),
+    ]
+}
+
+impl PersistedCollector {
+    fn collector(&self) -> Option<&dyn Collector> {
+        match self {
+            PersistedCollector::Counter(counter) => counter
+                .as_ref()
+                .ok()
+                .map(|counter| counter as &dyn Collector),
+            PersistedCollector::CounterVec(counter_vec) => counter_vec
+                .as_ref()
+                .ok()
+                .map(|counter_vec| counter_vec as &dyn Collector),
+        }
+    }
+
+    fn name(&self) -> Option<String> {
+        self.collector()?
+            .desc()
+            .first()
+            .map(|desc| desc.fq_name.clone())
+    }
+
+    /// Returns the current value of each time-series of this counter.
+    fn values(&self) -> Vec<PersistedCounter> {
+        self.collector()
+            .map(|collector| collector.collect())
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|family| {
+                family
+                    .get_metric()
+                    .iter()
+                    .map(move |metric| PersistedCounter {
+                        name: family.get_name().as_bytes().to_vec(),
+                        label_values: metric
+                            .get_label()
+                            .iter()
+                            .map(|label| label.get_value().as_bytes().to_vec())
+                            .collect(),
+                        value: metric.get_counter().get_value() as u64,
+                    })
+            })
+            .collect()
+    }
+
+    /// Adds `persisted.value` to the time-series of this counter identified by
+    /// `persisted.label_values`.
+    fn restore(&self, persisted: &PersistedCounter) {
+        match self {
+            PersistedCollector::Counter(counter) => {
+                metrics::inc_counter_by(counter, persisted.value);
+            }
+            PersistedCollector::CounterVec(counter_vec) => {
+                let label_values = persisted
+                    .label_values
+                    .iter()
+                    .map(|value| String::from_utf8_lossy(value).into_owned())
+                    .collect::<Vec<_>>();
+                let label_values = label_values.iter().map(String::as_str).collect::<Vec<_>>();
+                metrics::inc_counter_vec_by(counter_vec, &label_values, persisted.value);
+            }
+        }
+    }
+}
+
+/// The value of a single time-series of a counter.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedCounter {
+    /// The UTF-8 encoded name of the counter.
+    pub name: Vec<u8>,
+    /// The UTF-8 encoded label values of the time-series, in the order they were declared.
+    pub label_values: Vec<Vec<u8>>,
+    pub value: u64,
+}
+
+/// The values of all persisted counters.
+#[derive(Debug, Clone, PartialEq, Default, Encode, Decode)]
+pub struct PersistedMetrics {
+    pub counters: Vec<PersistedCounter>,
+}
+
+impl PersistedMetrics {
+    /// Reads the current values of all persisted counters.
+    pub fn from_current_values() -> Self {
+        Self {
+            counters: persisted_collectors()
+                .iter()
+                .flat_map(PersistedCollector::values)
+                .collect(),
+        }
+    }
+
+    /// Adds the values in `self` to the current values of the counters.
+    ///
+    /// This should be called once at startup. Any counters which are no longer persisted are
+    /// ignored.
+    pub fn restore(&self) {
+        let collectors = persisted_collectors();
+        for persisted in &self.counters {
+            if let Some(collector) = collectors.iter().find(|collector| {
+                collector
+                    .name()
+                    .map_or(false, |name| name.as_bytes() == persisted.name.as_slice())
+            }) {
+                collector.restore(persisted);
+            }
+        }
+    }
+}
+
+impl StoreItem for PersistedMetrics {
+    fn db_column() -> DBColumn {
+        DBColumn::PersistedMetrics
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> std::result::Result<Self, Error> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon_chain::METRICS_DB_KEY;
+    use store::{ItemStore, MemoryStore};
+    use types::MinimalEthSpec;
+
+    fn block_total(validator: &str) -> u64 {
+        metrics::get_int_counter(
+            &metrics::VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL,
+            &["persisted_metrics_test", validator],
+        )
+        .expect("should get counter")
+        .get()
+    }
+
+    #[test]
+    fn round_trip_through_store() {
+        metrics::inc_counter_vec_by(
+            &metrics::VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL,
+            &["persisted_metrics_test", "1"],
+            3,
+        );
+        metrics::inc_counter_vec_by(
+            &metrics::VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL,
+            &["persisted_metrics_test", "2"],
+            5,
+        );
+
+        let store = MemoryStore::<MinimalEthSpec>::open();
+        store
+            .put_item(&METRICS_DB_KEY, &PersistedMetrics::from_current_values())
+            .expect("should store metrics");
+        let persisted = store
+            .get_item::<PersistedMetrics>(&METRICS_DB_KEY)
+            .expect("should read metrics")
+            .expect("metrics should be present");
+
+        // Restoring adds the persisted values to the current values, as they would be after a
+        // restart if the counters had kept counting.
+        persisted.restore();
+        assert_eq!(block_total("1"), 6);
+        assert_eq!(block_total("2"), 10);
+    }
+}
@@ -0,0 +1,79 @@
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error, StoreItem};
+use types::{Epoch, PublicKeyBytes};
+
+/// The set of validators that were registered with the `ValidatorMonitor` via the HTTP API.
+///
+/// Validators supplied on the command line are not persisted, since they will be supplied again
+/// when the node is restarted.
+#[derive(Encode, Decode, Default)]
+pub struct PersistedValidatorMonitor {
+    pub pubkeys: Vec<PublicKeyBytes>,
+}
+
+impl StoreItem for PersistedValidatorMonitor {
+    fn db_column() -> DBColumn {
+        DBColumn::ValidatorMonitor
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> std::result::Result<Self, Error> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+/// The per-epoch summary of a monitored validator, as stored on disk.
+///
+/// Durations are stored in milliseconds.
+#[derive(Debug, Clone, PartialEq, Encode, Decode, Default)]
+pub struct PersistedEpochSummary {
+    pub epoch: Epoch,
+    pub attestations: u64,
+    pub attestation_min_delay_ms: Option<u64>,
+    pub attestation_aggregate_inclusions: u64,
+    pub attestation_block_inclusions: u64,
+    pub attestation_min_block_inclusion_distance: Option<u64>,
+    pub blocks: u64,
+    pub block_min_delay_ms: Option<u64>,
+    pub aggregates: u64,
+    pub aggregate_min_delay_ms: Option<u64>,
+    pub exits: u64,
+    pub proposer_slashings: u64,
+    pub attester_slashings: u64,
+    pub on_chain_attester: Option<bool>,
+    pub on_chain_head_attester: Option<bool>,
+    pub on_chain_target_attester: Option<bool>,
+    pub on_chain_inclusion_distance: Option<u64>,
+}
+
+/// The recent epoch summaries of a single monitored validator.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedValidatorSummaries {
+    pub pubkey: PublicKeyBytes,
+    pub summaries: Vec<PersistedEpochSummary>,
+}
+
+/// The recent epoch summaries of all monitored validators, so that the per-epoch metrics are not
+/// reset by a restart.
+#[derive(Debug, Clone, PartialEq, Encode, Decode, Default)]
+pub struct PersistedValidatorMonitorSummaries {
+    pub validators: Vec<PersistedValidatorSummaries>,
+}
+
+impl StoreItem for PersistedValidatorMonitorSummaries {
+    fn db_column() -> DBColumn {
+        DBColumn::ValidatorMonitorSummaries
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> std::result::Result<Self, Error> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
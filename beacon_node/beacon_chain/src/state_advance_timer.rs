@@ -20,14 +20,14 @@ use crate::{
     BeaconChain, BeaconChainError, BeaconChainTypes,
 };
 use slog::{debug, error, warn, Logger};
-use slot_clock::SlotClock;
+use slot_clock::{SlotEvent, SlotEventBus, SlotOffset};
 use state_processing::per_slot_processing;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
 use task_executor::TaskExecutor;
-use tokio::time::sleep;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
 use types::{AttestationShufflingId, EthSpec, Hash256, RelativeEpoch, Slot};
 
 /// If the head slot is more than `MAX_ADVANCE_DISTANCE` from the current slot, then don't perform
@@ -37,6 +37,12 @@ use types::{AttestationShufflingId, EthSpec, Hash256, RelativeEpoch, Slot};
 /// for some period of time.
 const MAX_ADVANCE_DISTANCE: u64 = 4;
 
+/// The point in each slot at which the head state is advanced.
+const STATE_ADVANCE_OFFSET: SlotOffset = SlotOffset::Fraction {
+    numerator: 3,
+    denominator: 4,
+};
+
 #[derive(Debug)]
 enum Error {
     BeaconChain(BeaconChainError),
@@ -78,10 +84,12 @@ impl Lock {
 pub fn spawn_state_advance_timer<T: BeaconChainTypes>(
     executor: TaskExecutor,
     beacon_chain: Arc<BeaconChain<T>>,
+    slot_event_bus: &SlotEventBus,
     log: Logger,
 ) {
+    let slot_events = slot_event_bus.subscribe(&[STATE_ADVANCE_OFFSET]);
     executor.spawn(
-        state_advance_timer(executor.clone(), beacon_chain, log),
+        state_advance_timer(executor.clone(), beacon_chain, slot_events, log),
         "state_advance_timer",
     );
 }
@@ -90,21 +98,28 @@ pub fn spawn_state_advance_timer<T: BeaconChainTypes>(
 async fn state_advance_timer<T: BeaconChainTypes>(
     executor: TaskExecutor,
     beacon_chain: Arc<BeaconChain<T>>,
+    mut slot_events: Receiver<SlotEvent>,
     log: Logger,
 ) {
     let is_running = Lock::new();
-    let slot_clock = &beacon_chain.slot_clock;
-    let slot_duration = slot_clock.slot_duration();
 
     loop {
-        match beacon_chain.slot_clock.duration_to_next_slot() {
-            Some(duration) => sleep(duration + (slot_duration / 4) * 3).await,
-            None => {
-                error!(log, "Failed to read slot clock");
-                // If we can't read the slot clock, just wait another slot.
-                sleep(slot_duration).await;
+        match slot_events.recv().await {
+            Ok(event) if event.offset == STATE_ADVANCE_OFFSET => (),
+            // Ignore events for offsets registered by other services.
+            Ok(_) => continue,
+            Err(RecvError::Lagged(missed)) => {
+                warn!(
+                    log,
+                    "State advance timer missed slot events";
+                    "missed" => missed
+                );
                 continue;
             }
+            Err(RecvError::Closed) => {
+                error!(log, "Slot event bus closed");
+                return;
+            }
         };
 
         // Only start spawn the state advance task if the lock was previously free.
@@ -3,6 +3,9 @@
 //! This component should not affect consensus.
 
 use crate::metrics;
+use crate::persisted_validator_monitor::{
+    PersistedEpochSummary, PersistedValidatorMonitorSummaries, PersistedValidatorSummaries,
+};
 use parking_lot::RwLock;
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -69,6 +72,17 @@ struct EpochSummary {
     pub proposer_slashings: usize,
     /// The number of attester slashings observed.
     pub attester_slashings: usize,
+    /*
+     * On-chain attestation performance, determined during the following epoch transitions.
+     */
+    /// `true` if an attestation was included on-chain.
+    pub on_chain_attester: Option<bool>,
+    /// `true` if an attestation with the correct head was included on-chain.
+    pub on_chain_head_attester: Option<bool>,
+    /// `true` if an attestation with the correct target was included on-chain.
+    pub on_chain_target_attester: Option<bool>,
+    /// The inclusion distance of the attestation included on-chain.
+    pub on_chain_inclusion_distance: Option<u64>,
 }
 
 impl EpochSummary {
@@ -116,10 +130,80 @@ impl EpochSummary {
     pub fn register_attester_slashing(&mut self) {
         self.attester_slashings += 1;
     }
+
+    fn to_persisted(&self, epoch: Epoch) -> PersistedEpochSummary {
+        let as_millis = |delay: Option<Duration>| delay.map(|delay| delay.as_millis() as u64);
+
+        PersistedEpochSummary {
+            epoch,
+            attestations: self.attestations as u64,
+            attestation_min_delay_ms: as_millis(self.attestation_min_delay),
+            attestation_aggregate_inclusions: self.attestation_aggregate_incusions as u64,
+            attestation_block_inclusions: self.attestation_block_inclusions as u64,
+            attestation_min_block_inclusion_distance: self
+                .attestation_min_block_inclusion_distance
+                .map(Slot::as_u64),
+            blocks: self.blocks as u64,
+            block_min_delay_ms: as_millis(self.block_min_delay),
+            aggregates: self.aggregates as u64,
+            aggregate_min_delay_ms: as_millis(self.aggregate_min_delay),
+            exits: self.exits as u64,
+            proposer_slashings: self.proposer_slashings as u64,
+            attester_slashings: self.attester_slashings as u64,
+            on_chain_attester: self.on_chain_attester,
+            on_chain_head_attester: self.on_chain_head_attester,
+            on_chain_target_attester: self.on_chain_target_attester,
+            on_chain_inclusion_distance: self.on_chain_inclusion_distance,
+        }
+    }
+
+    fn from_persisted(persisted: &PersistedEpochSummary) -> Self {
+        let from_millis = |delay: Option<u64>| delay.map(Duration::from_millis);
+
+        Self {
+            attestations: persisted.attestations as usize,
+            attestation_min_delay: from_millis(persisted.attestation_min_delay_ms),
+            attestation_aggregate_incusions: persisted.attestation_aggregate_inclusions as usize,
+            attestation_block_inclusions: persisted.attestation_block_inclusions as usize,
+            attestation_min_block_inclusion_distance: persisted
+                .attestation_min_block_inclusion_distance
+                .map(Slot::new),
+            blocks: persisted.blocks as usize,
+            block_min_delay: from_millis(persisted.block_min_delay_ms),
+            aggregates: persisted.aggregates as usize,
+            aggregate_min_delay: from_millis(persisted.aggregate_min_delay_ms),
+            exits: persisted.exits as usize,
+            proposer_slashings: persisted.proposer_slashings as usize,
+            attester_slashings: persisted.attester_slashings as usize,
+            on_chain_attester: persisted.on_chain_attester,
+            on_chain_head_attester: persisted.on_chain_head_attester,
+            on_chain_target_attester: persisted.on_chain_target_attester,
+            on_chain_inclusion_distance: persisted.on_chain_inclusion_distance,
+        }
+    }
 }
 
 type SummaryMap = HashMap<Epoch, EpochSummary>;
 
+/// Removes the oldest summaries until at most `HISTORIC_EPOCHS` remain.
+fn prune_summaries(summaries: &mut SummaryMap) {
+    while summaries.len() > HISTORIC_EPOCHS {
+        if let Some(key) = summaries.iter().map(|(epoch, _)| *epoch).min() {
+            summaries.remove(&key);
+        }
+    }
+}
+
+/// Returns `true` if a summary for `epoch` is recent enough to be kept during `current_epoch`.
+fn is_recent_epoch(epoch: Epoch, current_epoch: Epoch) -> bool {
+    epoch + HISTORIC_EPOCHS as u64 >= current_epoch
+}
+
+/// Removes all summaries which are older than `HISTORIC_EPOCHS` before `current_epoch`.
+fn retain_recent_summaries(summaries: &mut Vec<PersistedEpochSummary>, current_epoch: Epoch) {
+    summaries.retain(|summary| is_recent_epoch(summary.epoch, current_epoch));
+}
+
 /// A validator that is being monitored by the `ValidatorMonitor`.
 struct MonitoredValidator {
     /// A human-readable identifier for the validator.
@@ -170,12 +254,19 @@ impl MonitoredValidator {
 
         func(summaries.entry(epoch).or_default());
 
-        // Prune
-        while summaries.len() > HISTORIC_EPOCHS {
-            if let Some(key) = summaries.iter().map(|(epoch, _)| *epoch).min() {
-                summaries.remove(&key);
-            }
+        prune_summaries(&mut summaries);
+    }
+
+    /// Adds the `persisted` summaries to `self.summaries`, ignoring any epochs which already have
+    /// a summary.
+    fn restore_summaries(&self, persisted: &[PersistedEpochSummary]) {
+        let mut summaries = self.summaries.write();
+        for summary in persisted {
+            summaries
+                .entry(summary.epoch)
+                .or_insert_with(|| EpochSummary::from_persisted(summary));
         }
+        prune_summaries(&mut summaries);
     }
 }
 
@@ -194,16 +285,31 @@ pub struct ValidatorMonitor<T> {
     indices: HashMap<u64, PublicKeyBytes>,
     /// If true, allow the automatic registration of validators.
     auto_register: bool,
+    /// Summaries read from disk for validators which are not currently monitored. They are
+    /// restored if the validator is added to `self.validators` and are persisted again until then.
+    restored_summaries: HashMap<PublicKeyBytes, Vec<PersistedEpochSummary>>,
+    /// The delay after the start of a slot at which unaggregated attestations are produced.
+    unagg_attestation_production_delay: Duration,
+    /// The delay after the start of a slot at which aggregated attestations are produced.
+    agg_attestation_production_delay: Duration,
     log: Logger,
     _phantom: PhantomData<T>,
 }
 
 impl<T: EthSpec> ValidatorMonitor<T> {
-    pub fn new(pubkeys: Vec<PublicKeyBytes>, auto_register: bool, log: Logger) -> Self {
+    pub fn new(
+        pubkeys: Vec<PublicKeyBytes>,
+        auto_register: bool,
+        spec: &ChainSpec,
+        log: Logger,
+    ) -> Self {
         let mut s = Self {
             validators: <_>::default(),
             indices: <_>::default(),
             auto_register,
+            restored_summaries: <_>::default(),
+            unagg_attestation_production_delay: spec.unagg_attestation_production_delay(),
+            agg_attestation_production_delay: spec.agg_attestation_production_delay(),
             log,
             _phantom: PhantomData,
         };
@@ -214,7 +320,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     }
 
     /// Add some validators to `self` for additional monitoring.
-    fn add_validator_pubkey(&mut self, pubkey: PublicKeyBytes) {
+    pub fn add_validator_pubkey(&mut self, pubkey: PublicKeyBytes) {
         let index_opt = self
             .indices
             .iter()
@@ -222,19 +328,47 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             .map(|(index, _)| *index);
 
         let log = self.log.clone();
+        let restored_summaries = &mut self.restored_summaries;
         self.validators.entry(pubkey).or_insert_with(|| {
             info!(
                 log,
                 "Started monitoring validator";
                 "pubkey" => %pubkey,
             );
-            MonitoredValidator::new(pubkey, index_opt)
+            let validator = MonitoredValidator::new(pubkey, index_opt);
+            if let Some(summaries) = restored_summaries.remove(&pubkey) {
+                validator.restore_summaries(&summaries);
+            }
+            validator
         });
     }
 
+    /// Remove a validator from `self`, so that it no longer receives additional monitoring.
+    ///
+    /// Returns `true` if the validator was being monitored.
+    pub fn remove_validator_pubkey(&mut self, pubkey: &PublicKeyBytes) -> bool {
+        let removed = self.validators.remove(pubkey).is_some();
+
+        if removed {
+            info!(
+                self.log,
+                "Stopped monitoring validator";
+                "pubkey" => %pubkey,
+            );
+        }
+
+        removed
+    }
+
     /// Reads information from the given `state`. The `state` *must* be valid (i.e, able to be
     /// imported).
     pub fn process_valid_state(&mut self, current_epoch: Epoch, state: &BeaconState<T>) {
+        // Drop any restored summaries which have become too old to be of interest.
+        self.restored_summaries.retain(|_, summaries| {
+            retain_recent_summaries(summaries, current_epoch);
+            !summaries.is_empty()
+        });
+
         // Add any new validator indices.
         state
             .validators
@@ -418,11 +552,90 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                             inclusion_info.delay as i64,
                         );
                     }
+
+                    if summary.is_active_in_previous_epoch {
+                        monitored_validator.with_epoch_summary(prev_epoch, |epoch_summary| {
+                            epoch_summary.on_chain_attester =
+                                Some(summary.is_previous_epoch_attester);
+                            epoch_summary.on_chain_head_attester =
+                                Some(summary.is_previous_epoch_head_attester);
+                            epoch_summary.on_chain_target_attester =
+                                Some(summary.is_previous_epoch_target_attester);
+                            epoch_summary.on_chain_inclusion_distance =
+                                summary.inclusion_info.map(|info| info.delay);
+                        });
+                    }
                 }
             }
         }
     }
 
+    /// Returns the recent epoch summaries of all monitored validators, for persistence.
+    ///
+    /// Restored summaries of validators which have not been added to `self` yet are included, so
+    /// that they are not lost before the validator is registered. Summaries older than
+    /// `HISTORIC_EPOCHS` before `current_epoch` are omitted.
+    pub fn to_persisted_summaries(
+        &self,
+        current_epoch: Epoch,
+    ) -> PersistedValidatorMonitorSummaries {
+        let monitored = self.validators.values().map(|validator| {
+            let mut summaries = validator
+                .summaries
+                .read()
+                .iter()
+                .filter(|(epoch, _)| is_recent_epoch(**epoch, current_epoch))
+                .map(|(epoch, summary)| summary.to_persisted(*epoch))
+                .collect::<Vec<_>>();
+            summaries.sort_by_key(|summary| summary.epoch);
+
+            PersistedValidatorSummaries {
+                pubkey: validator.pubkey,
+                summaries,
+            }
+        });
+        let restored = self.restored_summaries.iter().map(|(pubkey, summaries)| {
+            let mut summaries = summaries.clone();
+            retain_recent_summaries(&mut summaries, current_epoch);
+            PersistedValidatorSummaries {
+                pubkey: *pubkey,
+                summaries,
+            }
+        });
+
+        PersistedValidatorMonitorSummaries {
+            validators: monitored
+                .chain(restored)
+                .filter(|validator| !validator.summaries.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Restores the epoch summaries returned by `Self::to_persisted_summaries`.
+    ///
+    /// Summaries for validators which are not monitored yet are kept until the validator is added
+    /// to `self`. Summaries for epochs which already have a summary in `self`, or which are older
+    /// than `HISTORIC_EPOCHS` before `current_epoch`, are ignored.
+    pub fn restore_summaries(
+        &mut self,
+        persisted: PersistedValidatorMonitorSummaries,
+        current_epoch: Epoch,
+    ) {
+        for mut persisted_validator in persisted.validators {
+            retain_recent_summaries(&mut persisted_validator.summaries, current_epoch);
+            if persisted_validator.summaries.is_empty() {
+                continue;
+            }
+
+            if let Some(validator) = self.validators.get(&persisted_validator.pubkey) {
+                validator.restore_summaries(&persisted_validator.summaries);
+            } else {
+                self.restored_summaries
+                    .insert(persisted_validator.pubkey, persisted_validator.summaries);
+            }
+        }
+    }
+
     fn get_validator_id(&self, validator_index: u64) -> Option<&str> {
         self.indices
             .get(&validator_index)
@@ -457,10 +670,11 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                     "validator" => %validator_index,
                 );
 
-                self.validators.insert(
-                    *pubkey,
-                    MonitoredValidator::new(*pubkey, Some(validator_index)),
-                );
+                let validator = MonitoredValidator::new(*pubkey, Some(validator_index));
+                if let Some(summaries) = self.restored_summaries.remove(pubkey) {
+                    validator.restore_summaries(&summaries);
+                }
+                self.validators.insert(*pubkey, validator);
             }
         }
     }
@@ -518,8 +732,9 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     }
 
     /// Returns the duration between when the attestation `data` could be produced (1/3rd through
-    /// the slot) and `seen_timestamp`.
+    /// the slot, by default) and `seen_timestamp`.
     fn get_unaggregated_attestation_delay_ms<S: SlotClock>(
+        &self,
         seen_timestamp: Duration,
         data: &AttestationData,
         slot_clock: &S,
@@ -528,7 +743,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             .start_of(data.slot)
             .and_then(|slot_start| seen_timestamp.checked_sub(slot_start))
             .and_then(|gross_delay| {
-                gross_delay.checked_sub(slot_clock.unagg_attestation_production_delay())
+                gross_delay.checked_sub(self.unagg_attestation_production_delay)
             })
             .unwrap_or_else(|| Duration::from_secs(0))
     }
@@ -572,7 +787,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     ) {
         let data = &indexed_attestation.data;
         let epoch = data.slot.epoch(T::slots_per_epoch());
-        let delay = Self::get_unaggregated_attestation_delay_ms(seen_timestamp, data, slot_clock);
+        let delay = self.get_unaggregated_attestation_delay_ms(seen_timestamp, data, slot_clock);
 
         indexed_attestation.attesting_indices.iter().for_each(|i| {
             if let Some(validator) = self.get_validator(*i) {
@@ -608,8 +823,9 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     }
 
     /// Returns the duration between when a `AggregateAndproof` with `data` could be produced (2/3rd
-    /// through the slot) and `seen_timestamp`.
+    /// through the slot, by default) and `seen_timestamp`.
     fn get_aggregated_attestation_delay_ms<S: SlotClock>(
+        &self,
         seen_timestamp: Duration,
         data: &AttestationData,
         slot_clock: &S,
@@ -617,9 +833,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         slot_clock
             .start_of(data.slot)
             .and_then(|slot_start| seen_timestamp.checked_sub(slot_start))
-            .and_then(|gross_delay| {
-                gross_delay.checked_sub(slot_clock.agg_attestation_production_delay())
-            })
+            .and_then(|gross_delay| gross_delay.checked_sub(self.agg_attestation_production_delay))
             .unwrap_or_else(|| Duration::from_secs(0))
     }
 
@@ -667,7 +881,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     ) {
         let data = &indexed_attestation.data;
         let epoch = data.slot.epoch(T::slots_per_epoch());
-        let delay = Self::get_aggregated_attestation_delay_ms(seen_timestamp, data, slot_clock);
+        let delay = self.get_aggregated_attestation_delay_ms(seen_timestamp, data, slot_clock);
 
         let aggregator_index = signed_aggregate_and_proof.message.aggregator_index;
         if let Some(validator) = self.get_validator(aggregator_index) {
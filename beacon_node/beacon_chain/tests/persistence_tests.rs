@@ -5,6 +5,7 @@ extern crate lazy_static;
 
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy},
+    validator_monitor::HISTORIC_EPOCHS,
     BeaconChain, BeaconChainTypes,
 };
 use sloggers::{null::NullLoggerBuilder, Build};
@@ -135,6 +136,208 @@ fn finalizes_after_resuming_from_db() {
     );
 }
 
+#[test]
+fn restores_validator_monitor_summaries_after_resuming_from_db() {
+    let validator_count = 16;
+    let pubkey = KEYPAIRS[0].pk.compress();
+
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let harness = BeaconChainHarness::new_with_disk_store(
+        MinimalEthSpec,
+        store.clone(),
+        KEYPAIRS[0..validator_count].to_vec(),
+    );
+    harness
+        .chain
+        .validator_monitor
+        .write()
+        .add_validator_pubkey(pubkey);
+
+    harness.advance_slot();
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let current_epoch = harness.chain.epoch().expect("should read epoch");
+    let summaries = harness
+        .chain
+        .validator_monitor
+        .read()
+        .to_persisted_summaries(current_epoch);
+    assert_eq!(
+        summaries.validators.len(),
+        1,
+        "the monitored validator should have summaries"
+    );
+    harness
+        .chain
+        .persist_validator_monitor_summaries()
+        .expect("should persist the validator monitor summaries");
+
+    let data_dir = harness.data_dir;
+    drop(harness.chain);
+
+    let resumed_harness = BeaconChainHarness::resume_from_disk_store(
+        MinimalEthSpec,
+        store.clone(),
+        KEYPAIRS[0..validator_count].to_vec(),
+        data_dir,
+    );
+
+    // The validator is only registered after start-up, as it would be with automatic registration.
+    assert_eq!(
+        resumed_harness
+            .chain
+            .validator_monitor
+            .read()
+            .num_validators(),
+        0
+    );
+    assert_eq!(
+        resumed_harness
+            .chain
+            .validator_monitor
+            .read()
+            .to_persisted_summaries(current_epoch),
+        summaries,
+        "summaries should be kept until the validator is registered"
+    );
+    resumed_harness
+        .chain
+        .validator_monitor
+        .write()
+        .add_validator_pubkey(pubkey);
+    assert_eq!(
+        resumed_harness
+            .chain
+            .validator_monitor
+            .read()
+            .to_persisted_summaries(current_epoch),
+        summaries,
+        "summaries should be restored when the validator is registered"
+    );
+
+    // Once the validator is no longer monitored, there is nothing to persist and the summaries on
+    // disk must be cleared.
+    resumed_harness
+        .chain
+        .validator_monitor
+        .write()
+        .remove_validator_pubkey(&pubkey);
+    resumed_harness
+        .chain
+        .persist_validator_monitor_summaries()
+        .expect("should persist the validator monitor summaries");
+
+    let data_dir = resumed_harness.data_dir;
+    drop(resumed_harness.chain);
+
+    let resumed_harness = BeaconChainHarness::resume_from_disk_store(
+        MinimalEthSpec,
+        store,
+        KEYPAIRS[0..validator_count].to_vec(),
+        data_dir,
+    );
+    resumed_harness
+        .chain
+        .validator_monitor
+        .write()
+        .add_validator_pubkey(pubkey);
+    assert!(
+        resumed_harness
+            .chain
+            .validator_monitor
+            .read()
+            .to_persisted_summaries(current_epoch)
+            .validators
+            .is_empty(),
+        "stale summaries should not be restored"
+    );
+}
+
+#[test]
+fn drops_old_validator_monitor_summaries() {
+    let validator_count = 16;
+    let pubkey = KEYPAIRS[0].pk.compress();
+
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let harness = BeaconChainHarness::new_with_disk_store(
+        MinimalEthSpec,
+        store,
+        KEYPAIRS[0..validator_count].to_vec(),
+    );
+    harness
+        .chain
+        .validator_monitor
+        .write()
+        .add_validator_pubkey(pubkey);
+
+    harness.advance_slot();
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let current_epoch = harness.chain.epoch().expect("should read epoch");
+    let mut validator_monitor = harness.chain.validator_monitor.write();
+    let summaries = validator_monitor.to_persisted_summaries(current_epoch);
+    let epochs = summaries.validators[0]
+        .summaries
+        .iter()
+        .map(|summary| summary.epoch)
+        .collect::<Vec<_>>();
+    assert!(
+        epochs.len() > 1,
+        "there should be summaries for several epochs"
+    );
+    let oldest_epoch = epochs[0];
+    let newest_epoch = epochs[epochs.len() - 1];
+
+    // At `later_epoch` the oldest summary is no longer recent enough to be persisted.
+    let later_epoch = oldest_epoch + HISTORIC_EPOCHS as u64 + 1;
+    let later_summaries = validator_monitor.to_persisted_summaries(later_epoch);
+    assert_eq!(
+        later_summaries.validators[0]
+            .summaries
+            .iter()
+            .map(|summary| summary.epoch)
+            .collect::<Vec<_>>(),
+        epochs[1..].to_vec(),
+        "the oldest summary should not be persisted"
+    );
+
+    // Once every summary is too old, nothing is persisted or restored.
+    let stale_epoch = newest_epoch + HISTORIC_EPOCHS as u64 + 1;
+    assert!(validator_monitor
+        .to_persisted_summaries(stale_epoch)
+        .validators
+        .is_empty());
+    validator_monitor.remove_validator_pubkey(&pubkey);
+    validator_monitor.restore_summaries(summaries.clone(), stale_epoch);
+    assert!(
+        validator_monitor
+            .to_persisted_summaries(current_epoch)
+            .validators
+            .is_empty(),
+        "stale summaries should not be restored"
+    );
+
+    // Summaries which are still recent are restored, the older ones are dropped.
+    validator_monitor.restore_summaries(summaries, later_epoch);
+    assert_eq!(
+        validator_monitor.to_persisted_summaries(current_epoch),
+        later_summaries,
+        "only recent summaries should be restored"
+    );
+}
+
 /// Checks that two chains are the same, for the purpose of this tests.
 ///
 /// Several fields that are hard/impossible to check are ignored (e.g., the store).
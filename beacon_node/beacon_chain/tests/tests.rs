@@ -5,6 +5,7 @@ extern crate lazy_static;
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    events::EventKind,
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
         OP_POOL_DB_KEY,
@@ -16,6 +17,7 @@ use state_processing::{
     per_slot_processing, per_slot_processing::Error as SlotProcessingError, EpochProcessingError,
 };
 use store::config::StoreConfig;
+use tokio::sync::broadcast::error::TryRecvError;
 use types::{BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot};
 
 // Should ideally be divisible by 3.
@@ -188,6 +190,66 @@ fn chooses_fork() {
     );
 }
 
+#[test]
+fn dependent_root_event_on_reorg() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let minority_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+    let majority_validators: Vec<usize> = (0..two_thirds).collect();
+
+    // Build a chain up to the penultimate slot of the first epoch.
+    let initial_blocks = MinimalEthSpec::slots_per_epoch() as usize - 2;
+    harness.extend_chain(
+        initial_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    let initial_head = harness
+        .chain
+        .head_info()
+        .expect("should get head")
+        .block_root;
+
+    let mut events = harness
+        .chain
+        .event_handler
+        .as_ref()
+        .expect("harness should have an event handler")
+        .subscribe_dependent_root();
+
+    // The minority fork includes a block in the last slot of the first epoch, which the majority
+    // fork skips. Once the majority fork becomes the head, the dependent root of the duties in the
+    // second epoch changes.
+    let (minority_head, majority_head) = harness.generate_two_forks_by_skipping_a_block(
+        &minority_validators,
+        &majority_validators,
+        2,
+        3,
+    );
+    harness.chain.fork_choice().expect("should run fork choice");
+
+    let head = harness.chain.head_info().expect("should get head");
+    assert_eq!(head.block_root, majority_head, "the chain should re-org");
+    assert_ne!(head.block_root, minority_head);
+
+    // Older events may have been dropped by the bounded channel, only the latest is of interest.
+    let event = loop {
+        match events.try_recv() {
+            Ok(event) => break event,
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(e) => panic!("a dependent root event should be emitted: {:?}", e),
+        }
+    };
+    match event {
+        EventKind::DependentRoot(event) => {
+            assert_eq!(event.current_duty_dependent_root, initial_head);
+            assert!(event.slot >= Slot::new(MinimalEthSpec::slots_per_epoch()));
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
+
 #[test]
 fn finalizes_with_full_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
@@ -4,8 +4,9 @@ use crate::Client;
 use beacon_chain::schema_change::migrate_schema;
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
+    disk_space_monitor::spawn_disk_space_monitor,
     eth1_chain::{CachingEth1Backend, Eth1Chain},
-    slot_clock::{SlotClock, SystemTimeSlotClock},
+    slot_clock::{SlotClock, SlotEventBus, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
     store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, ServerSentEventHandler,
@@ -499,9 +500,33 @@ where
         }
 
         if let Some(beacon_chain) = self.beacon_chain.as_ref() {
+            let slot_event_bus = SlotEventBus::new();
+            runtime_context.executor.spawn(
+                slot_event_bus.clone().run(beacon_chain.slot_clock.clone()),
+                "slot_event_bus",
+            );
+
             let state_advance_context = runtime_context.service_context("state_advance".into());
             let log = state_advance_context.log().clone();
-            spawn_state_advance_timer(state_advance_context.executor, beacon_chain.clone(), log);
+            spawn_state_advance_timer(
+                state_advance_context.executor,
+                beacon_chain.clone(),
+                &slot_event_bus,
+                log,
+            );
+
+            let disk_space_context = runtime_context.service_context("disk_space".into());
+            let log = disk_space_context.log().clone();
+            spawn_disk_space_monitor(
+                disk_space_context.executor,
+                beacon_chain.clone(),
+                self.db_path
+                    .iter()
+                    .chain(self.freezer_db_path.iter())
+                    .cloned()
+                    .collect(),
+                log,
+            );
         }
 
         Ok(Client {
@@ -1,6 +1,6 @@
 use crate::DepositLog;
 use ssz_derive::{Decode, Encode};
-use state_processing::common::DepositDataTree;
+use state_processing::common::{DepositDataTree, DepositTreeSnapshot};
 use std::cmp::Ordering;
 use tree_hash::TreeHash;
 use types::{Deposit, Hash256, DEPOSIT_TREE_DEPTH};
@@ -50,8 +50,6 @@ impl SszDepositCache {
     }
 
     pub fn to_deposit_cache(&self) -> Result<DepositCache, String> {
-        let deposit_tree =
-            DepositDataTree::create(&self.leaves, self.leaves.len(), DEPOSIT_TREE_DEPTH);
         // Check for invalid SszDepositCache conditions
         if self.leaves.len() != self.logs.len() {
             return Err("Invalid SszDepositCache: logs and leaves should have equal length".into());
@@ -63,6 +61,24 @@ impl SszDepositCache {
                     .into(),
             );
         }
+
+        // Ensure the leaves produce the latest persisted deposit root, so that a corrupted cache
+        // is detected here rather than producing invalid deposit proofs later.
+        let mut deposit_tree = DepositDataTree::create(&[], 0, DEPOSIT_TREE_DEPTH);
+        deposit_tree
+            .push_leaves(&self.leaves)
+            .map_err(|e| format!("Invalid SszDepositCache: {:?}", e))?;
+        let snapshot = DepositTreeSnapshot {
+            deposit_root: *self
+                .deposit_roots
+                .last()
+                .ok_or("Invalid SszDepositCache: deposit_roots is empty")?,
+            deposit_count: self.leaves.len() as u64,
+        };
+        deposit_tree
+            .verify_against_snapshot(&snapshot)
+            .map_err(|e| format!("Invalid SszDepositCache: {:?}", e))?;
+
         Ok(DepositCache {
             logs: self.logs.clone(),
             leaves: self.leaves.clone(),
@@ -453,4 +469,22 @@ pub mod tests {
         // Range higher than count.
         assert!(tree.get_deposits(0, 4, 2, TREE_DEPTH).is_err());
     }
+
+    #[test]
+    fn ssz_deposit_cache_detects_corrupt_leaves() {
+        let mut tree = DepositCache::default();
+
+        for i in 0..16 {
+            let mut log = example_log();
+            log.index = i;
+            log.deposit_data.withdrawal_credentials = Hash256::from_low_u64_be(i);
+            tree.insert_log(log).expect("should add consecutive logs");
+        }
+
+        let mut ssz_cache = SszDepositCache::from_deposit_cache(&tree);
+        assert!(ssz_cache.to_deposit_cache().is_ok());
+
+        ssz_cache.leaves[7] = Hash256::repeat_byte(42);
+        assert!(ssz_cache.to_deposit_cache().is_err());
+    }
 }
@@ -51,6 +51,13 @@ pub struct Config {
     /// Target number of connected peers.
     pub target_peers: usize,
 
+    /// Raise the target number of peers above `target_peers` whilst syncing or when many
+    /// validators are connected.
+    pub dynamic_target_peers: bool,
+
+    /// Controls the retry behaviour of parent block lookups.
+    pub parent_lookup: LookupConfig,
+
     /// Gossipsub configuration parameters.
     #[serde(skip)]
     pub gs_config: GossipsubConfig,
@@ -95,6 +102,30 @@ pub struct Config {
     pub topics: Vec<GossipKind>,
 }
 
+/// Controls how persistently the sync manager searches for the ancestors of a block with an unknown
+/// parent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LookupConfig {
+    /// The number of times a parent block may fail to download before the lookup is abandoned.
+    pub max_download_attempts: usize,
+    /// The number of times a peer may return a block which is not the requested parent before the
+    /// lookup is abandoned.
+    pub max_processing_attempts: usize,
+    /// The delay before retrying a failed request. The delay is multiplied by the number of failed
+    /// attempts so far.
+    pub retry_delay: Duration,
+}
+
+impl Default for LookupConfig {
+    fn default() -> Self {
+        Self {
+            max_download_attempts: 5,
+            max_processing_attempts: 3,
+            retry_delay: Duration::from_secs(0),
+        }
+    }
+}
+
 impl Default for Config {
     /// Generate a default network configuration.
     fn default() -> Self {
@@ -176,6 +207,8 @@ impl Default for Config {
             enr_udp_port: None,
             enr_tcp_port: None,
             target_peers: 50,
+            dynamic_target_peers: false,
+            parent_lookup: LookupConfig::default(),
             gs_config,
             discv5_config,
             boot_nodes_enr: vec![],
@@ -62,7 +62,7 @@ impl<'de> Deserialize<'de> for PeerIdSerialized {
 
 pub use crate::types::{error, Enr, GossipTopic, NetworkGlobals, PubsubMessage, SubnetDiscovery};
 pub use behaviour::{BehaviourEvent, Gossipsub, PeerRequestId, Request, Response};
-pub use config::Config as NetworkConfig;
+pub use config::{Config as NetworkConfig, LookupConfig};
 pub use discovery::{CombinedKeyExt, EnrExt, Eth2Enr};
 pub use discv5;
 pub use libp2p::bandwidth::BandwidthSinks;
@@ -72,7 +72,7 @@ pub use libp2p::{multiaddr, Multiaddr};
 pub use metrics::scrape_discovery_metrics;
 pub use peer_manager::{
     client::Client,
-    score::{PeerAction, ReportSource},
+    score::{PeerAction, PersistableScore, ReportSource},
     ConnectionDirection, PeerConnectionStatus, PeerDB, PeerInfo, PeerSyncStatus, SyncInfo,
 };
 pub use service::{load_private_key, Libp2pEvent, Service, NETWORK_KEY_FILENAME};
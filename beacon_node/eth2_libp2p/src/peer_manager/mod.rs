@@ -63,6 +63,20 @@ const ALLOWED_NEGATIVE_GOSSIPSUB_FACTOR: f32 = 0.1;
 /// A fraction of `PeerManager::target_peers` that need to be outbound-only connections.
 const MIN_OUTBOUND_ONLY_FACTOR: f32 = 0.1;
 
+/// When dynamic target peers are enabled, the factor by which the configured target peer count is
+/// raised whilst the node is syncing.
+const SYNCING_TARGET_PEERS_FACTOR: f32 = 1.5;
+/// When dynamic target peers are enabled, the number of local validators which warrant one extra
+/// peer above the configured target peer count.
+const VALIDATORS_PER_EXTRA_PEER: usize = 4;
+/// When dynamic target peers are enabled, the target peer count is never raised above this factor
+/// of the configured target peer count.
+const MAX_DYNAMIC_TARGET_PEERS_FACTOR: f32 = 2.0;
+/// The number of consecutive heartbeats in which a lower target peer count must be desired before
+/// the target is lowered. This prevents the target oscillating (and peers being churned) when the
+/// sync state or validator count fluctuates.
+const TARGET_PEERS_DECREASE_HEARTBEATS: usize = 10;
+
 /// The main struct that handles peer's reputation and connection status.
 pub struct PeerManager<TSpec: EthSpec> {
     /// Storage of network globals to access the `PeerDB`.
@@ -79,6 +93,16 @@ pub struct PeerManager<TSpec: EthSpec> {
     target_peers: usize,
     /// The maximum number of peers we allow (exceptions for subnet peers)
     max_peers: usize,
+    /// The target number of peers supplied by the user.
+    base_target_peers: usize,
+    /// If true, `target_peers` is adjusted during the heartbeat based on the sync state and the
+    /// number of local validators.
+    dynamic_target_peers: bool,
+    /// The number of validators which are currently known to be connected to this node.
+    local_validator_count: usize,
+    /// The number of consecutive heartbeats in which the dynamic target peer count was lower than
+    /// `target_peers`.
+    target_peers_decrease_heartbeats: usize,
     /// The discovery service.
     discovery: Discovery<TSpec>,
     /// The heartbeat interval to perform routine maintenance.
@@ -126,7 +150,11 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             outbound_ping_peers: HashSetDelay::new(Duration::from_secs(PING_INTERVAL_OUTBOUND)),
             status_peers: HashSetDelay::new(Duration::from_secs(STATUS_INTERVAL)),
             target_peers: config.target_peers,
-            max_peers: (config.target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR)).ceil() as usize,
+            max_peers: max_peers(config.target_peers),
+            base_target_peers: config.target_peers,
+            dynamic_target_peers: config.dynamic_target_peers,
+            local_validator_count: 0,
+            target_peers_decrease_heartbeats: 0,
             discovery,
             heartbeat,
             log: log.clone(),
@@ -135,6 +163,13 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
 
     /* Public accessible functions */
 
+    /// Informs the peer manager of the number of validators currently connected to this node.
+    ///
+    /// This is only used when dynamic target peers are enabled.
+    pub fn set_local_validator_count(&mut self, count: usize) {
+        self.local_validator_count = count;
+    }
+
     /// Attempts to connect to a peer.
     ///
     /// Returns true if the peer was accepted into the database.
@@ -916,6 +951,8 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     ///
     /// NOTE: Discovery will only add a new query if one isn't already queued.
     fn heartbeat(&mut self) {
+        self.update_target_peers();
+
         let peer_count = self.network_globals.connected_or_dialing_peers();
         let mut outbound_only_peer_count = self.network_globals.connected_outbound_only_peers();
         let min_outbound_only_target =
@@ -972,6 +1009,89 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             ));
         }
     }
+
+    /// Adjusts `target_peers` (and `max_peers`) when dynamic target peers are enabled.
+    ///
+    /// Increases to the target are applied immediately, whilst decreases are only applied once
+    /// they have been desired for `TARGET_PEERS_DECREASE_HEARTBEATS` consecutive heartbeats.
+    fn update_target_peers(&mut self) {
+        if !self.dynamic_target_peers {
+            return;
+        }
+
+        let desired = dynamic_target_peers(
+            self.base_target_peers,
+            self.network_globals.is_syncing(),
+            self.local_validator_count,
+        );
+
+        let new_target = match desired.cmp(&self.target_peers) {
+            Ordering::Greater => Some(desired),
+            Ordering::Less => {
+                self.target_peers_decrease_heartbeats += 1;
+                if self.target_peers_decrease_heartbeats >= TARGET_PEERS_DECREASE_HEARTBEATS {
+                    Some(desired)
+                } else {
+                    None
+                }
+            }
+            Ordering::Equal => None,
+        };
+
+        if desired >= self.target_peers {
+            self.target_peers_decrease_heartbeats = 0;
+        }
+
+        if let Some(new_target) = new_target {
+            debug!(
+                self.log,
+                "Updated target peer count";
+                "previous" => self.target_peers,
+                "new" => new_target,
+                "syncing" => self.network_globals.is_syncing(),
+                "local_validators" => self.local_validator_count,
+            );
+            self.target_peers = new_target;
+            self.max_peers = max_peers(new_target);
+            self.target_peers_decrease_heartbeats = 0;
+        }
+
+        metrics::set_gauge(&metrics::TARGET_PEERS, self.target_peers as i64);
+    }
+}
+
+/// Returns the maximum number of peers we allow for the given `target_peers`.
+fn max_peers(target_peers: usize) -> usize {
+    (target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR)).ceil() as usize
+}
+
+/// Returns the highest value that `PeerManager::target_peers` may take for the given config.
+pub fn max_target_peers(config: &NetworkConfig) -> usize {
+    if config.dynamic_target_peers {
+        (config.target_peers as f32 * MAX_DYNAMIC_TARGET_PEERS_FACTOR).ceil() as usize
+    } else {
+        config.target_peers
+    }
+}
+
+/// Returns the desired target peer count given the user-supplied `base_target_peers`.
+///
+/// The target is raised whilst syncing and for each `VALIDATORS_PER_EXTRA_PEER` local validators,
+/// but never above `MAX_DYNAMIC_TARGET_PEERS_FACTOR` of `base_target_peers`.
+fn dynamic_target_peers(
+    base_target_peers: usize,
+    is_syncing: bool,
+    local_validator_count: usize,
+) -> usize {
+    let syncing_target = if is_syncing {
+        (base_target_peers as f32 * SYNCING_TARGET_PEERS_FACTOR).ceil() as usize
+    } else {
+        base_target_peers
+    };
+    let validator_target = base_target_peers + local_validator_count / VALIDATORS_PER_EXTRA_PEER;
+    let cap = (base_target_peers as f32 * MAX_DYNAMIC_TARGET_PEERS_FACTOR).ceil() as usize;
+
+    std::cmp::min(std::cmp::max(syncing_target, validator_target), cap)
 }
 
 impl<TSpec: EthSpec> Stream for PeerManager<TSpec> {
@@ -1352,4 +1472,59 @@ mod tests {
         // the number of connected peers updates and we will not remove too many peers.
         assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
     }
+
+    #[test]
+    fn test_dynamic_target_peers() {
+        // Steady state.
+        assert_eq!(dynamic_target_peers(50, false, 0), 50);
+        // Syncing.
+        assert_eq!(dynamic_target_peers(50, true, 0), 75);
+        // Validator load.
+        assert_eq!(dynamic_target_peers(50, false, 3), 50);
+        assert_eq!(dynamic_target_peers(50, false, 40), 60);
+        // The greater of the two is used.
+        assert_eq!(dynamic_target_peers(50, true, 40), 75);
+        assert_eq!(dynamic_target_peers(50, true, 160), 90);
+        // The target is capped.
+        assert_eq!(dynamic_target_peers(50, false, 1_000), 100);
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_dynamic_target_peers_hysteresis() {
+        let mut peer_manager = build_peer_manager(10).await;
+        peer_manager.dynamic_target_peers = true;
+
+        peer_manager
+            .network_globals
+            .set_sync_state(SyncState::SyncTransition);
+        peer_manager.update_target_peers();
+        // Increases are applied immediately.
+        assert_eq!(peer_manager.target_peers, 15);
+        assert_eq!(peer_manager.max_peers, max_peers(15));
+
+        peer_manager
+            .network_globals
+            .set_sync_state(SyncState::Synced);
+        for _ in 1..TARGET_PEERS_DECREASE_HEARTBEATS {
+            peer_manager.update_target_peers();
+            assert_eq!(peer_manager.target_peers, 15);
+        }
+
+        // Decreases are only applied after enough consecutive heartbeats.
+        peer_manager.update_target_peers();
+        assert_eq!(peer_manager.target_peers, 10);
+        assert_eq!(peer_manager.max_peers, max_peers(10));
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_static_target_peers() {
+        let mut peer_manager = build_peer_manager(10).await;
+
+        peer_manager
+            .network_globals
+            .set_sync_state(SyncState::SyncTransition);
+        peer_manager.set_local_validator_count(100);
+        peer_manager.update_target_peers();
+        assert_eq!(peer_manager.target_peers, 10);
+    }
 }
@@ -124,6 +124,11 @@ impl<T: EthSpec> PeerInfo<T> {
         self.score.state()
     }
 
+    /// Replaces the score of the peer, e.g. when restoring it from disk.
+    pub(super) fn set_score(&mut self, score: Score) {
+        self.score = score;
+    }
+
     /// Applies decay rates to a non-trusted peer's score.
     pub fn score_update(&mut self) {
         if !self.is_trusted {
@@ -1,6 +1,6 @@
 use super::peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use super::peer_sync_status::PeerSyncStatus;
-use super::score::{Score, ScoreState};
+use super::score::{PersistableScore, Score, ScoreState};
 use crate::multiaddr::{Multiaddr, Protocol};
 use crate::rpc::methods::MetaData;
 use crate::Enr;
@@ -9,7 +9,7 @@ use rand::seq::SliceRandom;
 use slog::{crit, debug, error, trace, warn};
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use types::{EthSpec, SubnetId};
 
 /// Max number of disconnected nodes to remember.
@@ -19,6 +19,9 @@ const MAX_BANNED_PEERS: usize = 1000;
 /// We ban an IP if there are more than `BANNED_PEERS_PER_IP_THRESHOLD` banned peers with this IP.
 const BANNED_PEERS_PER_IP_THRESHOLD: usize = 5;
 
+/// Peers with a lighthouse score above this value are not worth persisting across restarts.
+const MAX_PERSISTED_SCORE: f64 = -1.0;
+
 /// Storage of known peers, their reputation and information
 pub struct PeerDB<TSpec: EthSpec> {
     /// The collection of known connected peers, their status and reputation
@@ -565,6 +568,43 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         Ok(())
     }
 
+    /// Returns the scores of all peers which should be retained across restarts. Trusted peers and
+    /// peers with a near-neutral score are omitted.
+    pub fn persistable_scores(&self) -> Vec<(PeerId, PersistableScore)> {
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, info)| {
+                info.score()
+                    .to_persistable()
+                    .filter(|score| score.lighthouse_score <= MAX_PERSISTED_SCORE)
+                    .map(|score| (*peer_id, score))
+            })
+            .collect()
+    }
+
+    /// Restores the score of a peer which was persisted `elapsed` ago. Peers whose restored score
+    /// is low enough are banned.
+    ///
+    /// Peers which are already known are ignored, since their current score is more recent.
+    pub fn restore_score(&mut self, peer_id: PeerId, score: PersistableScore, elapsed: Duration) {
+        if self.peers.contains_key(&peer_id) {
+            return;
+        }
+
+        let mut info = PeerInfo::default();
+        info.set_score(Score::from_persistable(score, elapsed));
+
+        if let ScoreState::Banned = info.score_state() {
+            debug!(self.log, "Restoring banned peer"; "peer_id" => %peer_id, "score" => %info.score());
+            info.ban();
+            self.banned_peers_count
+                .add_banned_peer(info.seen_addresses());
+        }
+
+        self.peers.insert(peer_id, info);
+        self.shrink_to_fit();
+    }
+
     /// Removes banned and disconnected peers from the DB if we have reached any of our limits.
     /// Drops the peers with the lowest reputation so that the number of
     /// disconnected peers is less than MAX_DC_PEERS
@@ -1089,4 +1129,32 @@ mod tests {
             Score::max_score().score()
         );
     }
+
+    #[test]
+    fn test_persisted_scores_round_trip() {
+        let mut pdb = get_db();
+        let bad_peer = PeerId::random();
+        let banned_peer = PeerId::random();
+        let good_peer = PeerId::random();
+
+        for peer in &[bad_peer, banned_peer, good_peer] {
+            pdb.connect_ingoing(peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        }
+        add_score(&mut pdb, &bad_peer, -30.0);
+        add_score(&mut pdb, &banned_peer, -100.0);
+
+        let scores = pdb.persistable_scores();
+        assert_eq!(scores.len(), 2);
+        assert!(!scores.iter().any(|(peer_id, _)| *peer_id == good_peer));
+
+        let mut pdb = get_db();
+        for (peer_id, score) in scores {
+            pdb.restore_score(peer_id, score, Duration::from_secs(0));
+        }
+
+        assert!(!pdb.is_banned(&bad_peer));
+        assert!(pdb.is_banned(&banned_peer));
+        assert_eq!(pdb.banned_peers().count(), 1);
+        assert_eq!(pdb.banned_peers_count.banned_peers(), 1);
+    }
 }
@@ -118,6 +118,15 @@ impl std::fmt::Display for ScoreState {
     }
 }
 
+/// The parts of a peer's score which are retained across restarts.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PersistableScore {
+    /// The lighthouse component of the score. Gossipsub scores are not persisted.
+    pub lighthouse_score: f64,
+    /// The remaining time before a banned peer's score begins to decay.
+    pub ban_remaining: Duration,
+}
+
 /// A peer's score (perceived potential usefulness).
 ///
 /// This simplistic version consists of a global score per peer which decays to 0 over time. The
@@ -263,6 +272,39 @@ impl RealScore {
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.gossipsub_score >= 0.0
     }
+
+    fn to_persistable(&self, now: Instant) -> PersistableScore {
+        PersistableScore {
+            lighthouse_score: self.lighthouse_score,
+            // `last_updated` is in the future whilst a banned peer's score is not decaying.
+            ban_remaining: self
+                .last_updated
+                .checked_duration_since(now)
+                .unwrap_or_else(|| Duration::from_secs(0)),
+        }
+    }
+
+    /// Restores a score which was persisted `elapsed` ago, applying the decay which would have
+    /// occurred during that time.
+    fn from_persistable(persisted: PersistableScore, elapsed: Duration, now: Instant) -> Self {
+        let mut score = RealScore {
+            lighthouse_score: persisted.lighthouse_score,
+            last_updated: now,
+            ..RealScore::default()
+        };
+
+        if let Some(ban_remaining) = persisted.ban_remaining.checked_sub(elapsed) {
+            score.last_updated = now + ban_remaining;
+        } else {
+            let secs_decaying = elapsed - persisted.ban_remaining;
+            let decay_factor = (*HALFLIFE_DECAY * secs_decaying.as_secs() as f64).exp();
+            score.lighthouse_score *= decay_factor;
+        }
+
+        // Use `recompute_score` rather than `update_state` to avoid extending the ban.
+        score.recompute_score();
+        score
+    }
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize)]
@@ -328,6 +370,23 @@ impl Score {
             Self::Real(score) => score.is_good_gossipsub_peer(),
         }
     }
+
+    /// Returns the parts of the score which should be persisted, or `None` for trusted peers.
+    pub fn to_persistable(&self) -> Option<PersistableScore> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some(score.to_persistable(Instant::now())),
+        }
+    }
+
+    /// Restores a score which was persisted `elapsed` ago.
+    pub fn from_persistable(persisted: PersistableScore, elapsed: Duration) -> Self {
+        Self::Real(RealScore::from_persistable(
+            persisted,
+            elapsed,
+            Instant::now(),
+        ))
+    }
 }
 
 impl Eq for Score {}
@@ -420,4 +479,49 @@ mod tests {
         assert!(!score.is_good_gossipsub_peer());
         assert_eq!(score.score(), 0.0);
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_persisted_score_decays() {
+        let mut score = RealScore::default();
+        let now = Instant::now();
+        score.test_add(-20.0);
+
+        let persisted = score.to_persistable(now);
+        assert_eq!(persisted.ban_remaining, Duration::from_secs(0));
+
+        // A brief restart leaves the score largely intact.
+        let restored = RealScore::from_persistable(persisted, Duration::from_secs(0), now);
+        assert_eq!(restored.score(), -20.0);
+
+        // A restart of one halflife halves the score.
+        let restored =
+            RealScore::from_persistable(persisted, Duration::from_secs(SCORE_HALFLIFE as u64), now);
+        assert!((restored.score() - -10.0).abs() < 0.001);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_persisted_ban() {
+        let mut score = RealScore::default();
+        let now = Instant::now();
+        score.test_add(MIN_SCORE_BEFORE_BAN);
+
+        let persisted = score.to_persistable(now);
+        assert!(persisted.ban_remaining > Duration::from_secs(0));
+
+        // The ban continues for the remainder of its duration.
+        let elapsed = Duration::from_secs(60);
+        let restored = RealScore::from_persistable(persisted, elapsed, now);
+        assert_eq!(restored.score(), MIN_SCORE_BEFORE_BAN);
+        assert_eq!(
+            restored.last_updated,
+            now + persisted.ban_remaining - elapsed
+        );
+
+        // Once the ban has expired, the score decays for the remaining time.
+        let elapsed = persisted.ban_remaining + Duration::from_secs(SCORE_HALFLIFE as u64);
+        let restored = RealScore::from_persistable(persisted, elapsed, now);
+        assert!((restored.score() - MIN_SCORE_BEFORE_BAN / 2.0).abs() < 0.001);
+    }
 }
@@ -274,6 +274,78 @@ mod tests {
         assert_eq!(codec.decode(&mut min).unwrap_err(), RPCError::InvalidData);
     }
 
+    #[test]
+    fn test_decode_chunks_delivered_byte_by_byte() {
+        let protocol_id = ProtocolId::new(Protocol::Ping, Version::V1, Encoding::SSZSnappy);
+        let mut inbound_codec = SSZSnappyInboundCodec::<Spec>::new(protocol_id.clone(), 1_048_576);
+        let mut outbound_codec = SSZSnappyOutboundCodec::<Spec>::new(protocol_id, 1_048_576);
+
+        let responses = (0..3)
+            .map(|data| RPCResponse::Pong(crate::rpc::methods::Ping { data }))
+            .collect::<Vec<RPCResponse<Spec>>>();
+
+        let mut encoded = BytesMut::new();
+        for response in &responses {
+            inbound_codec
+                .encode(RPCCodedResponse::Success(response.clone()), &mut encoded)
+                .unwrap();
+        }
+
+        // The codecs re-use their buffers between chunks and between partial reads of a chunk.
+        let mut src = BytesMut::new();
+        let mut decoded = vec![];
+        while !encoded.is_empty() {
+            src.extend_from_slice(&encoded.split_to(1));
+            if let Some(response) = outbound_codec.decode(&mut src).unwrap() {
+                decoded.push(response);
+            }
+        }
+
+        assert_eq!(decoded, responses);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_snappy_frames_buffered() {
+        // Large enough to span several snappy frames.
+        let bytes = (0..200_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect::<Vec<_>>();
+        let max_compressed_len = snap::raw::max_compress_len(bytes.len()) as u64;
+
+        let mut writer = FrameEncoder::new(Vec::new());
+        writer.write_all(&bytes).unwrap();
+        writer.flush().unwrap();
+        let stream = writer.get_ref();
+
+        for end in (0..stream.len()).step_by(997) {
+            assert!(
+                !snappy_frames_buffered(&stream[..end], bytes.len(), max_compressed_len),
+                "a partial stream of {} bytes should not be decompressed",
+                end
+            );
+        }
+        assert!(snappy_frames_buffered(
+            stream,
+            bytes.len(),
+            max_compressed_len
+        ));
+    }
+
+    #[test]
+    fn test_encode_outside_ssz_limits() {
+        let protocol_id = ProtocolId::new(Protocol::Status, Version::V1, Encoding::SSZSnappy);
+        let mut inbound_codec = SSZSnappyInboundCodec::<Spec>::new(protocol_id, 1_048_576);
+
+        // A pong is not a valid response on the status protocol.
+        let response =
+            RPCCodedResponse::Success(RPCResponse::Pong(crate::rpc::methods::Ping { data: 1 }));
+        assert!(matches!(
+            inbound_codec.encode(response, &mut BytesMut::new()),
+            Err(RPCError::InternalError(_))
+        ));
+    }
+
     #[test]
     fn test_decode_malicious_status_message() {
         // 10 byte snappy stream identifier
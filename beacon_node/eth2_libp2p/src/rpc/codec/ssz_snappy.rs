@@ -25,6 +25,10 @@ pub struct SSZSnappyInboundCodec<TSpec: EthSpec> {
     len: Option<usize>,
     /// Maximum bytes that can be sent in one req/resp chunked responses.
     max_packet_size: usize,
+    /// Scratch space for compressing outgoing chunks, re-used between chunks.
+    encode_buffer: Vec<u8>,
+    /// Scratch space for decompressing incoming chunks, re-used between chunks.
+    decode_buffer: Vec<u8>,
     phantom: PhantomData<TSpec>,
 }
 
@@ -40,6 +44,8 @@ impl<T: EthSpec> SSZSnappyInboundCodec<T> {
             len: None,
             phantom: PhantomData,
             max_packet_size,
+            encode_buffer: vec![],
+            decode_buffer: vec![],
         }
     }
 }
@@ -53,6 +59,7 @@ impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZSnappyInboundCodec<
         item: RPCCodedResponse<TSpec>,
         dst: &mut BytesMut,
     ) -> Result<(), Self::Error> {
+        let is_success = matches!(item, RPCCodedResponse::Success(_));
         let bytes = match item {
             RPCCodedResponse::Success(resp) => match resp {
                 RPCResponse::Status(res) => res.as_ssz_bytes(),
@@ -72,19 +79,24 @@ impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZSnappyInboundCodec<
                 "attempting to encode data > max_packet_size",
             ));
         }
+        // Successful responses should be within the bounds that the peer will use to decode them.
+        if is_success
+            && self
+                .protocol
+                .rpc_response_limits::<TSpec>()
+                .is_out_of_bounds(bytes.len())
+        {
+            return Err(RPCError::InternalError(
+                "attempting to encode data outside the ssz limits of the protocol",
+            ));
+        }
         // Inserts the length prefix of the uncompressed bytes into dst
         // encoded as a unsigned varint
         self.inner
             .encode(bytes.len(), dst)
             .map_err(RPCError::from)?;
 
-        let mut writer = FrameEncoder::new(Vec::new());
-        writer.write_all(&bytes).map_err(RPCError::from)?;
-        writer.flush().map_err(RPCError::from)?;
-
-        // Write compressed bytes to `dst`
-        dst.extend_from_slice(writer.get_ref());
-        Ok(())
+        compress(&bytes, &mut self.encode_buffer, dst)
     }
 }
 
@@ -114,62 +126,50 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyInboundCodec<TSpec> {
         if length > self.max_packet_size || ssz_limits.is_out_of_bounds(length) {
             return Err(RPCError::InvalidData);
         }
-        // Calculate worst case compression length for given uncompressed length
-        let max_compressed_len = snap::raw::max_compress_len(length) as u64;
-
-        // Create a limit reader as a wrapper that reads only upto `max_compressed_len` from `src`.
-        let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
-        let mut reader = FrameDecoder::new(limit_reader);
-        let mut decoded_buffer = vec![0; length];
-
-        match reader.read_exact(&mut decoded_buffer) {
-            Ok(()) => {
-                // `n` is how many bytes the reader read in the compressed stream
-                let n = reader.get_ref().get_ref().position();
-                self.len = None;
-                let _read_bytes = src.split_to(n as usize);
-
-                // We need not check that decoded_buffer.len() is within bounds here
-                // since we have already checked `length` above.
-                match self.protocol.message_name {
-                    Protocol::Status => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCRequest::Status(StatusMessage::from_ssz_bytes(
-                            &decoded_buffer,
-                        )?))),
-                    },
-                    Protocol::Goodbye => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCRequest::Goodbye(
-                            GoodbyeReason::from_ssz_bytes(&decoded_buffer)?,
-                        ))),
-                    },
-                    Protocol::BlocksByRange => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCRequest::BlocksByRange(
-                            BlocksByRangeRequest::from_ssz_bytes(&decoded_buffer)?,
-                        ))),
-                    },
-                    Protocol::BlocksByRoot => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCRequest::BlocksByRoot(BlocksByRootRequest {
-                            block_roots: VariableList::from_ssz_bytes(&decoded_buffer)?,
-                        }))),
-                    },
-                    Protocol::Ping => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCRequest::Ping(Ping {
-                            data: u64::from_ssz_bytes(&decoded_buffer)?,
-                        }))),
-                    },
-                    // This case should be unreachable as `MetaData` requests are handled separately in the `InboundUpgrade`
-                    Protocol::MetaData => match self.protocol.version {
-                        Version::V1 => {
-                            if !decoded_buffer.is_empty() {
-                                Err(RPCError::InvalidData)
-                            } else {
-                                Ok(Some(RPCRequest::MetaData(PhantomData)))
-                            }
-                        }
-                    },
+        if decompress(src, length, &mut self.decode_buffer)?.is_none() {
+            return Ok(None);
+        }
+        self.len = None;
+        let decoded_buffer = &self.decode_buffer;
+
+        // We need not check that decoded_buffer.len() is within bounds here
+        // since we have already checked `length` above.
+        match self.protocol.message_name {
+            Protocol::Status => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCRequest::Status(StatusMessage::from_ssz_bytes(
+                    decoded_buffer,
+                )?))),
+            },
+            Protocol::Goodbye => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCRequest::Goodbye(GoodbyeReason::from_ssz_bytes(
+                    decoded_buffer,
+                )?))),
+            },
+            Protocol::BlocksByRange => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCRequest::BlocksByRange(
+                    BlocksByRangeRequest::from_ssz_bytes(decoded_buffer)?,
+                ))),
+            },
+            Protocol::BlocksByRoot => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCRequest::BlocksByRoot(BlocksByRootRequest {
+                    block_roots: VariableList::from_ssz_bytes(decoded_buffer)?,
+                }))),
+            },
+            Protocol::Ping => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCRequest::Ping(Ping {
+                    data: u64::from_ssz_bytes(decoded_buffer)?,
+                }))),
+            },
+            // This case should be unreachable as `MetaData` requests are handled separately in the `InboundUpgrade`
+            Protocol::MetaData => match self.protocol.version {
+                Version::V1 => {
+                    if !decoded_buffer.is_empty() {
+                        Err(RPCError::InvalidData)
+                    } else {
+                        Ok(Some(RPCRequest::MetaData(PhantomData)))
+                    }
                 }
-            }
-            Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
+            },
         }
     }
 }
@@ -181,6 +181,10 @@ pub struct SSZSnappyOutboundCodec<TSpec: EthSpec> {
     protocol: ProtocolId,
     /// Maximum bytes that can be sent in one req/resp chunked responses.
     max_packet_size: usize,
+    /// Scratch space for compressing outgoing chunks, re-used between chunks.
+    encode_buffer: Vec<u8>,
+    /// Scratch space for decompressing incoming chunks, re-used between chunks.
+    decode_buffer: Vec<u8>,
     phantom: PhantomData<TSpec>,
 }
 
@@ -195,6 +199,8 @@ impl<TSpec: EthSpec> SSZSnappyOutboundCodec<TSpec> {
             protocol,
             max_packet_size,
             len: None,
+            encode_buffer: vec![],
+            decode_buffer: vec![],
             phantom: PhantomData,
         }
     }
@@ -219,6 +225,16 @@ impl<TSpec: EthSpec> Encoder<RPCRequest<TSpec>> for SSZSnappyOutboundCodec<TSpec
                 "attempting to encode data > max_packet_size",
             ));
         }
+        // Requests should be within the bounds that the peer will use to decode them.
+        if self
+            .protocol
+            .rpc_request_limits()
+            .is_out_of_bounds(bytes.len())
+        {
+            return Err(RPCError::InternalError(
+                "attempting to encode data outside the ssz limits of the protocol",
+            ));
+        }
 
         // Inserts the length prefix of the uncompressed bytes into dst
         // encoded as a unsigned varint
@@ -226,13 +242,7 @@ impl<TSpec: EthSpec> Encoder<RPCRequest<TSpec>> for SSZSnappyOutboundCodec<TSpec
             .encode(bytes.len(), dst)
             .map_err(RPCError::from)?;
 
-        let mut writer = FrameEncoder::new(Vec::new());
-        writer.write_all(&bytes).map_err(RPCError::from)?;
-        writer.flush().map_err(RPCError::from)?;
-
-        // Write compressed bytes to `dst`
-        dst.extend_from_slice(writer.get_ref());
-        Ok(())
+        compress(&bytes, &mut self.encode_buffer, dst)
     }
 }
 
@@ -266,54 +276,42 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyOutboundCodec<TSpec> {
         if length > self.max_packet_size || ssz_limits.is_out_of_bounds(length) {
             return Err(RPCError::InvalidData);
         }
-        // Calculate worst case compression length for given uncompressed length
-        let max_compressed_len = snap::raw::max_compress_len(length) as u64;
-        // Create a limit reader as a wrapper that reads only upto `max_compressed_len` from `src`.
-        let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
-        let mut reader = FrameDecoder::new(limit_reader);
-
-        let mut decoded_buffer = vec![0; length];
-
-        match reader.read_exact(&mut decoded_buffer) {
-            Ok(()) => {
-                // `n` is how many bytes the reader read in the compressed stream
-                let n = reader.get_ref().get_ref().position();
-                self.len = None;
-                let _read_bytes = src.split_to(n as usize);
-
-                // We need not check that decoded_buffer.len() is within bounds here
-                // since we have already checked `length` above.
-                match self.protocol.message_name {
-                    Protocol::Status => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCResponse::Status(
-                            StatusMessage::from_ssz_bytes(&decoded_buffer)?,
-                        ))),
-                    },
-                    // This case should be unreachable as `Goodbye` has no response.
-                    Protocol::Goodbye => Err(RPCError::InvalidData),
-                    Protocol::BlocksByRange => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCResponse::BlocksByRange(Box::new(
-                            SignedBeaconBlock::from_ssz_bytes(&decoded_buffer)?,
-                        )))),
-                    },
-                    Protocol::BlocksByRoot => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCResponse::BlocksByRoot(Box::new(
-                            SignedBeaconBlock::from_ssz_bytes(&decoded_buffer)?,
-                        )))),
-                    },
-                    Protocol::Ping => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCResponse::Pong(Ping {
-                            data: u64::from_ssz_bytes(&decoded_buffer)?,
-                        }))),
-                    },
-                    Protocol::MetaData => match self.protocol.version {
-                        Version::V1 => Ok(Some(RPCResponse::MetaData(MetaData::from_ssz_bytes(
-                            &decoded_buffer,
-                        )?))),
-                    },
-                }
-            }
-            Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
+        if decompress(src, length, &mut self.decode_buffer)?.is_none() {
+            return Ok(None);
+        }
+        self.len = None;
+        let decoded_buffer = &self.decode_buffer;
+
+        // We need not check that decoded_buffer.len() is within bounds here
+        // since we have already checked `length` above.
+        match self.protocol.message_name {
+            Protocol::Status => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCResponse::Status(StatusMessage::from_ssz_bytes(
+                    decoded_buffer,
+                )?))),
+            },
+            // This case should be unreachable as `Goodbye` has no response.
+            Protocol::Goodbye => Err(RPCError::InvalidData),
+            Protocol::BlocksByRange => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCResponse::BlocksByRange(Box::new(
+                    SignedBeaconBlock::from_ssz_bytes(decoded_buffer)?,
+                )))),
+            },
+            Protocol::BlocksByRoot => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCResponse::BlocksByRoot(Box::new(
+                    SignedBeaconBlock::from_ssz_bytes(decoded_buffer)?,
+                )))),
+            },
+            Protocol::Ping => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCResponse::Pong(Ping {
+                    data: u64::from_ssz_bytes(decoded_buffer)?,
+                }))),
+            },
+            Protocol::MetaData => match self.protocol.version {
+                Version::V1 => Ok(Some(RPCResponse::MetaData(MetaData::from_ssz_bytes(
+                    decoded_buffer,
+                )?))),
+            },
         }
     }
 }
@@ -344,25 +342,104 @@ impl<TSpec: EthSpec> OutboundCodec<RPCRequest<TSpec>> for SSZSnappyOutboundCodec
             return Err(RPCError::InvalidData);
         }
 
-        // Calculate worst case compression length for given uncompressed length
-        let max_compressed_len = snap::raw::max_compress_len(length) as u64;
-        // Create a limit reader as a wrapper that reads only upto `max_compressed_len` from `src`.
-        let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
-        let mut reader = FrameDecoder::new(limit_reader);
-        let mut decoded_buffer = vec![0; length];
-        match reader.read_exact(&mut decoded_buffer) {
-            Ok(()) => {
-                // `n` is how many bytes the reader read in the compressed stream
-                let n = reader.get_ref().get_ref().position();
-                self.len = None;
-                let _read_bytes = src.split_to(n as usize);
-                Ok(Some(ErrorType(VariableList::from_ssz_bytes(
-                    &decoded_buffer,
-                )?)))
-            }
-            Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
+        if decompress(src, length, &mut self.decode_buffer)?.is_none() {
+            return Ok(None);
+        }
+        self.len = None;
+        let decoded_buffer = &self.decode_buffer;
+
+        Ok(Some(ErrorType(VariableList::from_ssz_bytes(
+            decoded_buffer,
+        )?)))
+    }
+}
+
+/// Writes `bytes` to `dst` as a snappy frame stream, using `buffer` as scratch space.
+fn compress(bytes: &[u8], buffer: &mut Vec<u8>, dst: &mut BytesMut) -> Result<(), RPCError> {
+    buffer.clear();
+    {
+        let mut writer = FrameEncoder::new(&mut *buffer);
+        writer.write_all(bytes).map_err(RPCError::from)?;
+        writer.flush().map_err(RPCError::from)?;
+    }
+
+    // Write compressed bytes to `dst`
+    dst.extend_from_slice(buffer);
+    Ok(())
+}
+
+/// Decompresses a snappy frame stream of `length` uncompressed bytes from the start of `src` into
+/// `buffer`, removing the compressed bytes from `src`.
+///
+/// Returns `Ok(None)` if `src` does not yet contain the entire stream. `buffer` is re-used between
+/// calls so that it is not re-allocated each time more bytes of a large chunk arrive.
+///
+/// The stream is only decompressed once all of its frames have been received, otherwise a large
+/// chunk arriving in many small reads would be decompressed from the start on every read.
+fn decompress(
+    src: &mut BytesMut,
+    length: usize,
+    buffer: &mut Vec<u8>,
+) -> Result<Option<()>, RPCError> {
+    // Calculate worst case compression length for given uncompressed length
+    let max_compressed_len = snap::raw::max_compress_len(length) as u64;
+
+    if !snappy_frames_buffered(src.as_ref(), length, max_compressed_len) {
+        return Ok(None);
+    }
+
+    // Create a limit reader as a wrapper that reads only upto `max_compressed_len` from `src`.
+    let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
+    let mut reader = FrameDecoder::new(limit_reader);
+
+    buffer.resize(length, 0);
+    match reader.read_exact(buffer) {
+        Ok(()) => {
+            // `n` is how many bytes the reader read in the compressed stream
+            let n = reader.get_ref().get_ref().position();
+            let _read_bytes = src.split_to(n as usize);
+            Ok(Some(()))
         }
+        Err(e) => handle_error(e, reader.get_ref().get_ref().position(), max_compressed_len),
+    }
+}
+
+/// Returns `true` if `src` contains enough snappy frames to decompress `length` bytes, or enough
+/// bytes for the decoder to reject the stream. Only the frame headers (and the uncompressed length
+/// at the start of compressed frames) are read, the frames are not decompressed.
+pub(crate) fn snappy_frames_buffered(src: &[u8], length: usize, max_compressed_len: u64) -> bool {
+    let mut offset = 0;
+    let mut uncompressed_len = 0;
+
+    while uncompressed_len < length && (offset as u64) < max_compressed_len {
+        // Each frame has a 1 byte type and a 3 byte little-endian length.
+        let frame_len = match src.get(offset..offset + 4) {
+            Some(header) => {
+                usize::from(header[1]) | usize::from(header[2]) << 8 | usize::from(header[3]) << 16
+            }
+            None => return src.len() as u64 >= max_compressed_len,
+        };
+        let frame = match src.get(offset + 4..offset + 4 + frame_len) {
+            Some(frame) => frame,
+            None => return src.len() as u64 >= max_compressed_len,
+        };
+
+        uncompressed_len += match src[offset] {
+            // Compressed data, preceded by a 4 byte checksum.
+            0x00 => match frame.get(4..).map(snap::raw::decompress_len) {
+                Some(Ok(len)) => len,
+                // Leave it to the decoder to reject the invalid frame.
+                _ => return true,
+            },
+            // Uncompressed data, preceded by a 4 byte checksum.
+            0x01 => frame_len.saturating_sub(4),
+            // The stream identifier, padding and other skippable frames.
+            _ => 0,
+        };
+        offset += 4 + frame_len;
     }
+
+    true
 }
 
 /// Handle errors that we get from decoding an RPC message from the stream.
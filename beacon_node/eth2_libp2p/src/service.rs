@@ -3,6 +3,7 @@ use crate::behaviour::{
 };
 use crate::discovery::enr;
 use crate::multiaddr::Protocol;
+use crate::peer_manager::max_target_peers;
 use crate::rpc::{GoodbyeReason, MetaData, RPCResponseErrorCode, RequestId};
 use crate::types::{error, EnrBitfield, GossipKind};
 use crate::EnrExt;
@@ -126,11 +127,12 @@ impl<TSpec: EthSpec> Service<TSpec> {
             }
 
             // sets up the libp2p connection limits
+            let max_target_peers = max_target_peers(config);
             let limits = ConnectionLimits::default()
                 .with_max_pending_incoming(Some(5))
                 .with_max_pending_outgoing(Some(16))
-                .with_max_established_incoming(Some((config.target_peers as f64 * 1.2) as u32))
-                .with_max_established_outgoing(Some((config.target_peers as f64 * 1.2) as u32))
+                .with_max_established_incoming(Some((max_target_peers as f64 * 1.2) as u32))
+                .with_max_established_outgoing(Some((max_target_peers as f64 * 1.2) as u32))
                 .with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
 
             (
@@ -15,5 +15,5 @@ pub type Enr = discv5::enr::Enr<discv5::enr::CombinedKey>;
 pub use globals::NetworkGlobals;
 pub use pubsub::{PubsubMessage, SnappyTransform};
 pub use subnet::SubnetDiscovery;
-pub use sync_state::SyncState;
+pub use sync_state::{BlockLookupInfo, BlockLookupKind, BlockLookupState, SyncState};
 pub use topics::{subnet_id_from_topic_hash, GossipEncoding, GossipKind, GossipTopic, CORE_TOPICS};
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use types::Slot;
+use types::{Hash256, Slot};
 
 /// The current state of the node.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -54,6 +54,46 @@ impl SyncState {
     }
 }
 
+/// The kind of a block lookup performed by the sync manager.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockLookupKind {
+    /// A lookup for a single block which was referenced by a peer (e.g., in an attestation).
+    Single,
+    /// A lookup for the ancestors of a block whose parent is unknown.
+    Parent,
+}
+
+/// The progress of a block lookup performed by the sync manager.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockLookupState {
+    /// A request for a block is in flight.
+    Downloading,
+    /// A block has been received and is being processed.
+    Processing,
+    /// A previous request failed and the lookup is waiting to be retried.
+    AwaitingRetry,
+}
+
+/// A snapshot of a block lookup in progress, used for debugging stuck lookups.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockLookupInfo {
+    pub kind: BlockLookupKind,
+    /// For a single lookup, the root of the requested block. For a parent lookup, the root of the
+    /// block which triggered the lookup.
+    pub block_root: Hash256,
+    pub state: BlockLookupState,
+    /// The number of blocks downloaded so far.
+    pub downloaded_blocks: usize,
+    /// The number of failed download attempts.
+    pub failed_download_attempts: usize,
+    /// The number of times an incorrect or invalid block was received.
+    pub failed_processing_attempts: usize,
+    /// The peers which have been involved in the lookup.
+    pub peers: Vec<String>,
+}
+
 impl std::fmt::Display for SyncState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1,7 +1,8 @@
 use beacon_chain::{BeaconChain, BeaconChainTypes, WhenSlotSkipped};
-use eth2::types::BlockId as CoreBlockId;
+use eth2::types::{BlockId as CoreBlockId, RelativeAnchor};
+use std::convert::TryFrom;
 use std::str::FromStr;
-use types::{Hash256, SignedBeaconBlock, Slot};
+use types::{EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 /// Wraps `eth2::types::BlockId` and provides a simple way to obtain a block or root for a given
 /// `BlockId`.
@@ -48,6 +49,9 @@ impl BlockId {
                     })
                 }),
             CoreBlockId::Root(root) => Ok(*root),
+            CoreBlockId::Relative { anchor, offset } => {
+                Self::from_slot(relative_slot(chain, *anchor, *offset)?).root(chain)
+            }
         }
     }
 
@@ -81,6 +85,9 @@ impl BlockId {
                         ))),
                     })
             }
+            CoreBlockId::Relative { anchor, offset } => {
+                Self::from_slot(relative_slot(chain, *anchor, *offset)?).block(chain)
+            }
             _ => {
                 let root = self.root(chain)?;
                 chain
@@ -99,6 +106,45 @@ impl BlockId {
     }
 }
 
+/// Returns the slot which is `offset` slots from the slot of `anchor`.
+///
+/// The slot of the finalized and justified anchors is the first slot of the checkpoint epoch.
+pub fn relative_slot<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    anchor: RelativeAnchor,
+    offset: i64,
+) -> Result<Slot, warp::Rejection> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let anchor_slot = match anchor {
+        RelativeAnchor::Genesis => chain.spec.genesis_slot,
+        RelativeAnchor::Head => chain
+            .head_info()
+            .map(|head| head.slot)
+            .map_err(warp_utils::reject::beacon_chain_error)?,
+        RelativeAnchor::Finalized => chain
+            .head_info()
+            .map(|head| head.finalized_checkpoint.epoch.start_slot(slots_per_epoch))
+            .map_err(warp_utils::reject::beacon_chain_error)?,
+        RelativeAnchor::Justified => chain
+            .head_info()
+            .map(|head| {
+                head.current_justified_checkpoint
+                    .epoch
+                    .start_slot(slots_per_epoch)
+            })
+            .map_err(warp_utils::reject::beacon_chain_error)?,
+    };
+
+    let slot = i128::from(anchor_slot.as_u64()) + i128::from(offset);
+
+    u64::try_from(slot).map(Slot::new).map_err(|_| {
+        warp_utils::reject::custom_bad_request(format!(
+            "{} slots from {} ({}) is not a valid slot",
+            offset, anchor, anchor_slot
+        ))
+    })
+}
+
 impl FromStr for BlockId {
     type Err = String;
 
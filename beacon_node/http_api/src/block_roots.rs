@@ -0,0 +1,127 @@
+//! Contains the handler for the `POST lighthouse/beacon/block_roots` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::ResolvedBlockRoot;
+use std::collections::HashMap;
+use types::{EthSpec, Hash256, Slot};
+
+/// The maximum number of block roots which may be resolved in a single request.
+pub const MAX_BLOCK_ROOTS_PER_REQUEST: usize = 1024;
+
+/// Resolves the slot and state root of each block in `block_roots`, along with whether the block
+/// is canonical and finalized.
+///
+/// Blocks are looked up in fork choice first, then in the database, so only blocks which are not
+/// known to fork choice (i.e., those prior to finalization) require a database read. Roots which
+/// are not known to this node are omitted from the result.
+pub fn resolve_block_roots<T: BeaconChainTypes>(
+    block_roots: Vec<Hash256>,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<ResolvedBlockRoot>, warp::Rejection> {
+    if block_roots.len() > MAX_BLOCK_ROOTS_PER_REQUEST {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "at most {} block roots may be requested",
+            MAX_BLOCK_ROOTS_PER_REQUEST
+        )));
+    }
+
+    let finalized_slot = chain
+        .head_info()
+        .map_err(warp_utils::reject::beacon_chain_error)?
+        .finalized_checkpoint
+        .epoch
+        .start_slot(T::EthSpec::slots_per_epoch());
+
+    let from_fork_choice = {
+        let fork_choice = chain.fork_choice.read();
+        block_roots
+            .iter()
+            .map(|root| {
+                fork_choice
+                    .get_block(root)
+                    .map(|block| (block.slot, block.state_root))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut blocks = Vec::with_capacity(block_roots.len());
+    for (block_root, fork_choice_block) in block_roots.into_iter().zip(from_fork_choice) {
+        let (slot, state_root) = match fork_choice_block {
+            Some(block) => block,
+            None => match chain
+                .get_block(&block_root)
+                .map_err(warp_utils::reject::beacon_chain_error)?
+            {
+                Some(block) => (block.slot(), block.state_root()),
+                None => continue,
+            },
+        };
+        blocks.push((block_root, slot, state_root));
+    }
+
+    let canonical_roots =
+        canonical_block_roots(chain, blocks.iter().map(|(_, slot, _)| *slot).collect())?;
+
+    Ok(blocks
+        .into_iter()
+        .map(|(block_root, slot, state_root)| {
+            let canonical = canonical_roots.get(&slot) == Some(&block_root);
+            ResolvedBlockRoot {
+                block_root,
+                slot,
+                state_root,
+                canonical,
+                finalized: canonical && slot <= finalized_slot,
+            }
+        })
+        .collect())
+}
+
+/// Returns the canonical block root at each of `slots`, using the root of the prior block for
+/// skipped slots.
+///
+/// Slots which are within the head state's `block_roots` are read from it directly. All remaining
+/// slots are resolved with a single forwards iteration, rather than one iteration per slot.
+fn canonical_block_roots<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    mut slots: Vec<Slot>,
+) -> Result<HashMap<Slot, Hash256>, warp::Rejection> {
+    slots.sort_unstable();
+    slots.dedup();
+
+    let mut canonical_roots = HashMap::with_capacity(slots.len());
+    let mut remaining_slots = vec![];
+    chain
+        .with_head::<_, BeaconChainError>(|head| {
+            for slot in slots {
+                if head.beacon_block.slot() <= slot {
+                    // All slots between the head and `slot` are skipped.
+                    canonical_roots.insert(slot, head.beacon_block_root);
+                } else if let Ok(root) = head.beacon_state.get_block_root(slot) {
+                    canonical_roots.insert(slot, *root);
+                } else {
+                    remaining_slots.push(slot);
+                }
+            }
+            Ok(())
+        })
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+    if let (Some(first_slot), Some(last_slot)) = (remaining_slots.first(), remaining_slots.last()) {
+        let last_slot = *last_slot;
+        let iter = chain
+            .forwards_iter_block_roots(*first_slot)
+            .map_err(warp_utils::reject::beacon_chain_error)?;
+        for result in iter {
+            let (root, slot) = result.map_err(warp_utils::reject::beacon_chain_error)?;
+            if slot > last_slot {
+                break;
+            }
+            if remaining_slots.binary_search(&slot).is_ok() {
+                canonical_roots.insert(slot, root);
+            }
+        }
+    }
+
+    Ok(canonical_roots)
+}
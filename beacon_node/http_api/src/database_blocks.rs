@@ -0,0 +1,93 @@
+//! Contains the handler for the `GET lighthouse/database/blocks` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::DatabaseBlocksQuery;
+use ssz::Encode;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use warp::http::Response;
+use warp::hyper::Body;
+
+/// The number of encoded blocks which may be buffered before the export waits for the client to
+/// read them.
+const BLOCK_EXPORT_BUFFER: usize = 16;
+
+/// Streams the canonical blocks from `query.start_slot` to `query.end_slot` (inclusive) as a
+/// chunked response.
+///
+/// Each block is SSZ encoded and prefixed with its length as a 4-byte little-endian integer.
+/// Blocks are read from the database one at a time as the client consumes the response, so
+/// large ranges can be exported without holding them all in memory.
+pub fn database_blocks<T: BeaconChainTypes>(
+    query: DatabaseBlocksQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Response<Body>, warp::Rejection> {
+    if query.start_slot > query.end_slot {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "start_slot ({}) must not be greater than end_slot ({})",
+            query.start_slot, query.end_slot
+        )));
+    }
+
+    let block_roots = chain
+        .forwards_iter_block_roots(query.start_slot)
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+    let (tx, rx) = mpsc::channel::<Result<Vec<u8>, String>>(BLOCK_EXPORT_BUFFER);
+
+    tokio::task::spawn_blocking(move || {
+        let mut prev_root = None;
+        for result in block_roots {
+            let (block_root, slot) = match result {
+                Ok(item) => item,
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(format!("Unable to read block roots: {:?}", e)));
+                    return;
+                }
+            };
+            if slot > query.end_slot {
+                break;
+            }
+            // Skipped slots repeat the root of the previous block.
+            if prev_root == Some(block_root) {
+                continue;
+            }
+            prev_root = Some(block_root);
+
+            let block = match chain.get_block(&block_root) {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    let _ = tx.blocking_send(Err(format!("Missing block {:?}", block_root)));
+                    return;
+                }
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(format!("Unable to read block: {:?}", e)));
+                    return;
+                }
+            };
+            // The first root may belong to a block prior to `start_slot` if it was skipped.
+            if block.slot() != slot {
+                continue;
+            }
+
+            let block_bytes = block.as_ssz_bytes();
+            let mut frame = Vec::with_capacity(4 + block_bytes.len());
+            frame.extend_from_slice(&(block_bytes.len() as u32).to_le_bytes());
+            frame.extend_from_slice(&block_bytes);
+
+            // An error indicates that the client has disconnected.
+            if tx.blocking_send(Ok(frame)).is_err() {
+                return;
+            }
+        }
+    });
+
+    Response::builder()
+        .status(200)
+        .header("Content-Type", "application/octet-stream")
+        .body(Body::wrap_stream(ReceiverStream::new(rx)))
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+        })
+}
@@ -0,0 +1,138 @@
+//! Contains the handler for the `GET lighthouse/ui/health_timeline` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::SlotHealth;
+use state_processing::common::get_attesting_indices;
+use std::collections::{HashMap, HashSet};
+use types::{BeaconState, BeaconStateError, EthSpec, Slot};
+
+/// Returns a summary of the health of the chain at each of the last `slots` slots, up to and
+/// including the current slot.
+///
+/// The summary is assembled from the head state, fork choice and the block times cache, so it
+/// does not read any blocks or states from the database besides the canonical block roots.
+pub fn health_timeline<T: BeaconChainTypes>(
+    slots: u64,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<SlotHealth>, warp::Rejection> {
+    let max_slots = T::EthSpec::slots_per_historical_root() as u64;
+    if slots == 0 || slots > max_slots {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "slots must be between 1 and {}",
+            max_slots
+        )));
+    }
+
+    let current_slot = chain
+        .slot()
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    let start_slot = (current_slot + 1).saturating_sub(slots);
+
+    // Map each slot to the root of the canonical block at or before it, including the slot prior to
+    // `start_slot` so that a skip at `start_slot` can be detected.
+    let mut roots_by_slot = HashMap::new();
+    for result in chain
+        .rev_iter_block_roots()
+        .map_err(warp_utils::reject::beacon_chain_error)?
+    {
+        let (root, slot) = result.map_err(warp_utils::reject::beacon_chain_error)?;
+        if slot + 1 < start_slot {
+            break;
+        }
+        roots_by_slot.insert(slot, root);
+    }
+
+    let canonical_roots = roots_by_slot.values().copied().collect::<HashSet<_>>();
+    let reorged_slots = chain
+        .fork_choice
+        .read()
+        .proto_array()
+        .core_proto_array()
+        .nodes
+        .iter()
+        .filter(|node| node.slot >= start_slot && !canonical_roots.contains(&node.root))
+        .map(|node| node.slot)
+        .collect::<HashSet<_>>();
+
+    let participation = chain
+        .with_head(|head| {
+            attestation_participation(&head.beacon_state, start_slot)
+                .map_err(BeaconChainError::BeaconStateError)
+        })
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+    let block_times_cache = chain.block_times_cache.read();
+
+    Ok((start_slot.as_u64()..=current_slot.as_u64())
+        .map(Slot::new)
+        .map(|slot| {
+            let previous_root = slot
+                .as_u64()
+                .checked_sub(1)
+                .and_then(|previous_slot| roots_by_slot.get(&Slot::new(previous_slot)));
+            let block_root = roots_by_slot
+                .get(&slot)
+                .filter(|root| Some(*root) != previous_root)
+                .copied();
+
+            SlotHealth {
+                slot,
+                block_root,
+                block_import_delay_ms: block_root
+                    .and_then(|root| block_times_cache.get_import_delay(&root))
+                    .map(|delay| delay.as_millis() as u64),
+                attestation_participation: participation.get(&slot).copied(),
+                reorged: reorged_slots.contains(&slot),
+            }
+        })
+        .collect())
+}
+
+/// Returns the proportion of committee members at each slot from `start_slot` whose attestations
+/// are included in `state`.
+///
+/// Only slots in the previous or current epoch of `state` which are prior to `state.slot` are
+/// returned, since attestations for other slots are either pruned or cannot yet be included.
+fn attestation_participation<E: EthSpec>(
+    state: &BeaconState<E>,
+    start_slot: Slot,
+) -> Result<HashMap<Slot, f64>, BeaconStateError> {
+    let mut attesters: HashMap<Slot, HashSet<usize>> = HashMap::new();
+    for attestation in state
+        .previous_epoch_attestations
+        .iter()
+        .chain(state.current_epoch_attestations.iter())
+        .filter(|attestation| attestation.data.slot >= start_slot)
+    {
+        let committee =
+            state.get_beacon_committee(attestation.data.slot, attestation.data.index)?;
+        let indices =
+            get_attesting_indices::<E>(committee.committee, &attestation.aggregation_bits)?;
+        attesters
+            .entry(attestation.data.slot)
+            .or_default()
+            .extend(indices);
+    }
+
+    let first_slot = std::cmp::max(
+        start_slot,
+        state.previous_epoch().start_slot(E::slots_per_epoch()),
+    );
+
+    let mut participation = HashMap::new();
+    for slot in (first_slot.as_u64()..state.slot.as_u64()).map(Slot::new) {
+        let committee_members = state
+            .get_beacon_committees_at_slot(slot)?
+            .iter()
+            .map(|committee| committee.committee.len())
+            .sum::<usize>();
+        if committee_members == 0 {
+            continue;
+        }
+
+        let attesting = attesters.get(&slot).map_or(0, HashSet::len);
+        participation.insert(slot, attesting as f64 / committee_members as f64);
+    }
+
+    Ok(participation)
+}
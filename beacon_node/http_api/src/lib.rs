@@ -7,9 +7,14 @@
 
 mod attester_duties;
 mod block_id;
+mod block_roots;
+mod database_blocks;
+mod health_timeline;
 mod metrics;
 mod proposer_duties;
+mod reorg_impact;
 mod state_id;
+mod validator_client_instances;
 mod validator_inclusion;
 
 use beacon_chain::{
@@ -20,7 +25,7 @@ use beacon_chain::{
     WhenSlotSkipped,
 };
 use block_id::BlockId;
-use eth2::types::{self as api_types, ValidatorId};
+use eth2::types::{self as api_types, ValidatorId, VALIDATOR_CLIENT_INSTANCE_HEADER};
 use eth2_libp2p::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
 use lighthouse_version::version_with_platform;
 use network::NetworkMessage;
@@ -34,12 +39,15 @@ use std::convert::TryInto;
 use std::future::Future;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::Arc;
-use tokio::sync::mpsc::UnboundedSender;
+use std::time::Duration;
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{
-    Attestation, AttesterSlashing, CommitteeCache, Epoch, EthSpec, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot, YamlConfig,
+    Attestation, AttesterSlashing, BeaconStateSummary, CommitteeCache, Epoch, EthSpec, Hash256,
+    ProposerSlashing, PublicKeyBytes, RelativeEpoch, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedVoluntaryExit, Slot, YamlConfig,
 };
+use validator_client_instances::ValidatorClientInstances;
 use warp::http::StatusCode;
 use warp::sse::Event;
 use warp::Reply;
@@ -56,6 +64,9 @@ const API_VERSION: &str = "v1";
 /// finalized head.
 const SYNC_TOLERANCE_EPOCHS: u64 = 8;
 
+/// The time to wait for the sync manager to report its block lookups.
+const SYNC_LOOKUPS_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// A wrapper around all the items required to spawn the HTTP server.
 ///
 /// The server will gracefully handle the case where any fields are `None`.
@@ -334,6 +345,11 @@ pub fn serve<T: BeaconChainTypes>(
     // Create a `warp` filter that provides access to the logger.
     let log_filter = warp::any().map(move || ctx.log.clone());
 
+    // Create a `warp` filter that provides access to the validator client instance tracker.
+    let validator_client_instances = Arc::new(ValidatorClientInstances::default());
+    let validator_client_instances_filter =
+        warp::any().map(move || validator_client_instances.clone());
+
     /*
      *
      * Start of HTTP method definitions.
@@ -957,21 +973,50 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::body::json())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(warp::header::optional::<String>(
+            VALIDATOR_CLIENT_INSTANCE_HEADER,
+        ))
+        .and(validator_client_instances_filter.clone())
         .and_then(
             |chain: Arc<BeaconChain<T>>,
              attestations: Vec<Attestation<T::EthSpec>>,
              network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-             log: Logger| {
+             log: Logger,
+             instance: Option<String>,
+             validator_client_instances: Arc<ValidatorClientInstances>| {
                 blocking_json_task(move || {
                     let seen_timestamp = timestamp_now();
                     let mut failures = Vec::new();
 
+                    if let Ok(epoch) = chain.epoch() {
+                        validator_client_instances.prune(epoch);
+                    }
+
                     for (index, attestation) in attestations.as_slice().iter().enumerate() {
                         let attestation = match chain
                             .verify_unaggregated_attestation_for_gossip(attestation.clone(), None)
                         {
                             Ok(attestation) => attestation,
                             Err(e) => {
+                                // An attestation from this validator has already been seen. If it
+                                // came from a different validator client then two clients are
+                                // likely running the same validator.
+                                if let (
+                                    AttnError::PriorAttestationKnown {
+                                        validator_index,
+                                        epoch,
+                                    },
+                                    Some(instance),
+                                ) = (&e, &instance)
+                                {
+                                    validator_client_instances.observe(
+                                        *validator_index,
+                                        instance,
+                                        *epoch,
+                                        &log,
+                                    );
+                                }
+
                                 error!(log,
                                     "Failure verifying attestation for gossip";
                                     "error" => ?e,
@@ -998,6 +1043,18 @@ pub fn serve<T: BeaconChainTypes>(
                                 &chain.slot_clock,
                             );
 
+                        if let Some(instance) = &instance {
+                            let indexed = attestation.indexed_attestation();
+                            for validator_index in indexed.attesting_indices.iter() {
+                                validator_client_instances.observe(
+                                    *validator_index,
+                                    instance,
+                                    indexed.data.target.epoch,
+                                    &log,
+                                );
+                            }
+                        }
+
                         publish_pubsub_message(
                             &network_tx,
                             PubsubMessage::Attestation(Box::new((
@@ -1049,22 +1106,38 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET beacon/pool/attestations?committee_index,slot
+    // GET beacon/pool/attestations?committee_index,slot,from_slot,to_slot,only_includable
     let get_beacon_pool_attestations = beacon_pool_path
         .clone()
         .and(warp::path("attestations"))
         .and(warp::path::end())
         .and(warp::query::<api_types::AttestationPoolQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .and_then(
-            |chain: Arc<BeaconChain<T>>, query: api_types::AttestationPoolQuery| {
-                blocking_json_task(move || {
+            |chain: Arc<BeaconChain<T>>,
+             query: api_types::AttestationPoolQuery,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    let current_slot = if query.only_includable == Some(true) {
+                        Some(
+                            chain
+                                .slot()
+                                .map_err(warp_utils::reject::beacon_chain_error)?,
+                        )
+                    } else {
+                        None
+                    };
+
                     let query_filter = |attestation: &Attestation<T::EthSpec>| {
-                        query
-                            .slot
-                            .map_or(true, |slot| slot == attestation.data.slot)
-                            && query
-                                .committee_index
-                                .map_or(true, |index| index == attestation.data.index)
+                        let data = &attestation.data;
+                        // An attestation may only be included in a block within the inclusion
+                        // window. Attestations from the current slot are yet to enter it.
+                        let is_includable = current_slot.map_or(true, |current_slot| {
+                            data.slot + chain.spec.min_attestation_inclusion_delay <= current_slot
+                                && current_slot <= data.slot + T::EthSpec::slots_per_epoch()
+                        });
+
+                        query.matches(data) && is_includable
                     };
 
                     let mut attestations = chain.op_pool.get_filtered_attestations(query_filter);
@@ -1076,7 +1149,23 @@ pub fn serve<T: BeaconChainTypes>(
                             .cloned()
                             .filter(query_filter),
                     );
-                    Ok(api_types::GenericResponse::from(attestations))
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Response::builder()
+                            .status(200)
+                            .header("Content-Type", "application/octet-stream")
+                            .body(attestations.as_ssz_bytes().into())
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            }),
+                        _ => Ok(
+                            warp::reply::json(&api_types::GenericResponse::from(attestations))
+                                .into_response(),
+                        ),
+                    }
                 })
             },
         );
@@ -1931,6 +2020,34 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/sync/lookups
+    let get_lighthouse_sync_lookups = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("lookups"))
+        .and(warp::path::end())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| async move {
+                let (sender, receiver) = oneshot::channel();
+                publish_network_message(&network_tx, NetworkMessage::GetBlockLookups(sender))?;
+                let lookups = tokio::time::timeout(SYNC_LOOKUPS_TIMEOUT, receiver)
+                    .await
+                    .map_err(|_| {
+                        warp_utils::reject::custom_service_unavailable(
+                            "timed out waiting for the sync manager".to_string(),
+                        )
+                    })?
+                    .map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "the sync manager did not respond".to_string(),
+                        )
+                    })?;
+                Ok::<_, warp::Rejection>(warp::reply::json(&api_types::GenericResponse::from(
+                    lookups,
+                )))
+            },
+        );
+
     // GET lighthouse/peers
     let get_lighthouse_peers = warp::path("lighthouse")
         .and(warp::path("peers"))
@@ -2109,6 +2226,74 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/beacon/states/{state_id}/summary
+    let get_lighthouse_beacon_states_summary = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("states"))
+        .and(warp::path::param::<StateId>())
+        .and(warp::path("summary"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and(chain_filter.clone())
+        .and_then(
+            |state_id: StateId,
+             accept_header: Option<api_types::Accept>,
+             chain: Arc<BeaconChain<T>>| {
+                blocking_task(move || {
+                    let mut state = state_id.state(&chain)?;
+                    state
+                        .build_committee_cache(RelativeEpoch::Current, &chain.spec)
+                        .map_err(warp_utils::reject::beacon_state_error)?;
+                    let summary = BeaconStateSummary::from_state(&state, &chain.spec)
+                        .map_err(warp_utils::reject::beacon_state_error)?;
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => Response::builder()
+                            .status(200)
+                            .header("Content-Type", "application/octet-stream")
+                            .body(summary.as_ssz_bytes().into())
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            }),
+                        _ => Ok(
+                            warp::reply::json(&api_types::GenericResponse::from(summary))
+                                .into_response(),
+                        ),
+                    }
+                })
+            },
+        );
+
+    // POST lighthouse/beacon/block_roots
+    let post_lighthouse_beacon_block_roots = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("block_roots"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(|block_roots: Vec<Hash256>, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                block_roots::resolve_block_roots(block_roots, &chain)
+                    .map(api_types::GenericResponse::from)
+            })
+        });
+
+    // GET lighthouse/database/blocks?start_slot,end_slot
+    let get_lighthouse_database_blocks = warp::path("lighthouse")
+        .and(warp::path("database"))
+        .and(warp::path("blocks"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::DatabaseBlocksQuery>())
+        .and(chain_filter.clone())
+        .and_then(
+            |query: eth2::lighthouse::DatabaseBlocksQuery, chain: Arc<BeaconChain<T>>| {
+                blocking_task(move || database_blocks::database_blocks(query, chain))
+            },
+        );
+
     // GET lighthouse/staking
     let get_lighthouse_staking = warp::path("lighthouse")
         .and(warp::path("staking"))
@@ -2128,6 +2313,101 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/duplicate_validator_clients
+    let get_lighthouse_duplicate_validator_clients = warp::path("lighthouse")
+        .and(warp::path("duplicate_validator_clients"))
+        .and(warp::path::end())
+        .and(validator_client_instances_filter)
+        .and_then(
+            |validator_client_instances: Arc<ValidatorClientInstances>| {
+                blocking_json_task(move || {
+                    Ok(api_types::GenericResponse::from(
+                        validator_client_instances.duplicates(),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/disk_space
+    let get_lighthouse_disk_space = warp::path("lighthouse")
+        .and(warp::path("disk_space"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                Ok(api_types::GenericResponse::from(
+                    eth2::lighthouse::DiskSpaceStatus {
+                        free_bytes: chain.disk_space.free_bytes(),
+                        threshold_bytes: chain.config.disk_space_threshold,
+                        protective_mode: chain.disk_space.is_protective_mode(),
+                    },
+                ))
+            })
+        });
+
+    // GET lighthouse/analysis/reorg_impact?root
+    let get_lighthouse_analysis_reorg_impact = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("reorg_impact"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::ReorgImpactQuery>())
+        .and(chain_filter.clone())
+        .and_then(
+            |query: eth2::lighthouse::ReorgImpactQuery, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    reorg_impact::reorg_impact(query.root, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/ui/health_timeline?slots
+    let get_lighthouse_ui_health_timeline = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("health_timeline"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::HealthTimelineQuery>())
+        .and(chain_filter.clone())
+        .and_then(
+            |query: eth2::lighthouse::HealthTimelineQuery, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    let slots = query.slots.unwrap_or_else(T::EthSpec::slots_per_epoch);
+                    health_timeline::health_timeline(slots, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/validator_monitor/validators
+    let post_lighthouse_validator_monitor_validators = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("validators"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(|pubkeys: Vec<PublicKeyBytes>, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                chain
+                    .register_monitored_validators(&pubkeys)
+                    .map_err(warp_utils::reject::beacon_chain_error)
+            })
+        });
+
+    // DELETE lighthouse/validator_monitor/validators
+    let delete_lighthouse_validator_monitor_validators = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("validators"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(|pubkeys: Vec<PublicKeyBytes>, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                chain
+                    .unregister_monitored_validators(&pubkeys)
+                    .map_err(warp_utils::reject::beacon_chain_error)
+            })
+        });
+
     let get_events = eth1_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -2153,6 +2433,9 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::FinalizedCheckpoint => {
                                     event_handler.subscribe_finalized()
                                 }
+                                api_types::EventTopic::DependentRoot => {
+                                    event_handler.subscribe_dependent_root()
+                                }
                             };
 
                             receivers.push(BroadcastStream::new(receiver).map(|msg| {
@@ -2224,6 +2507,7 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(get_validator_aggregate_attestation.boxed())
                 .or(get_lighthouse_health.boxed())
                 .or(get_lighthouse_syncing.boxed())
+                .or(get_lighthouse_sync_lookups.boxed())
                 .or(get_lighthouse_peers.boxed())
                 .or(get_lighthouse_peers_connected.boxed())
                 .or(get_lighthouse_proto_array.boxed())
@@ -2233,7 +2517,13 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(get_lighthouse_eth1_block_cache.boxed())
                 .or(get_lighthouse_eth1_deposit_cache.boxed())
                 .or(get_lighthouse_beacon_states_ssz.boxed())
+                .or(get_lighthouse_beacon_states_summary.boxed())
+                .or(get_lighthouse_database_blocks.boxed())
                 .or(get_lighthouse_staking.boxed())
+                .or(get_lighthouse_duplicate_validator_clients.boxed())
+                .or(get_lighthouse_disk_space.boxed())
+                .or(get_lighthouse_analysis_reorg_impact.boxed())
+                .or(get_lighthouse_ui_health_timeline.boxed())
                 .or(get_events.boxed()),
         )
         .or(warp::post().and(
@@ -2245,8 +2535,11 @@ pub fn serve<T: BeaconChainTypes>(
                 .or(post_beacon_pool_voluntary_exits.boxed())
                 .or(post_validator_duties_attester.boxed())
                 .or(post_validator_aggregate_and_proofs.boxed())
-                .or(post_validator_beacon_committee_subscriptions.boxed()),
+                .or(post_validator_beacon_committee_subscriptions.boxed())
+                .or(post_lighthouse_validator_monitor_validators.boxed())
+                .or(post_lighthouse_beacon_block_roots.boxed()),
         ))
+        .or(warp::delete().and(delete_lighthouse_validator_monitor_validators.boxed()))
         .recover(warp_utils::reject::handle_rejection)
         .with(slog_logging(log.clone()))
         .with(prometheus_metrics())
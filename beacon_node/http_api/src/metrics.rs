@@ -33,4 +33,8 @@ lazy_static::lazy_static! {
         "http_api_block_broadcast_delay_times",
         "Time between start of the slot and when the block was broadcast"
     );
+    pub static ref HTTP_API_DUPLICATE_VALIDATOR_CLIENTS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_duplicate_validator_clients_total",
+        "Count of validator messages received from a different validator client instance"
+    );
 }
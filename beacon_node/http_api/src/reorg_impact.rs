@@ -0,0 +1,193 @@
+//! Contains the handler for the `GET lighthouse/analysis/reorg_impact` endpoint.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::ReorgImpact;
+use std::collections::HashSet;
+use types::{Hash256, SignedBeaconBlock};
+
+/// Estimates the impact of fork choice switching from the current head to `candidate_root`.
+///
+/// Blocks are compared by walking both chains back to their common ancestor in fork choice, so
+/// `candidate_root` must be a non-finalized block which is known to fork choice.
+pub fn reorg_impact<T: BeaconChainTypes>(
+    candidate_root: Hash256,
+    chain: &BeaconChain<T>,
+) -> Result<ReorgImpact, warp::Rejection> {
+    let head_root = chain
+        .head_info()
+        .map_err(warp_utils::reject::beacon_chain_error)?
+        .block_root;
+
+    let (head_weight, candidate_weight, common_ancestor, orphaned_roots, candidate_roots) = {
+        let fork_choice = chain.fork_choice.read();
+        let proto_array = fork_choice.proto_array();
+
+        let weight = |root: &Hash256| {
+            proto_array.get_weight(root).ok_or_else(|| {
+                warp_utils::reject::custom_not_found(format!(
+                    "block {:?} is not known to fork choice",
+                    root
+                ))
+            })
+        };
+        let head_weight = weight(&head_root)?;
+        let candidate_weight = weight(&candidate_root)?;
+
+        let head_ancestors = proto_array
+            .core_proto_array()
+            .iter_block_roots(&head_root)
+            .map(|(root, _)| root)
+            .collect::<HashSet<_>>();
+
+        let mut candidate_roots = vec![];
+        let mut common_ancestor = None;
+        for (root, slot) in proto_array
+            .core_proto_array()
+            .iter_block_roots(&candidate_root)
+        {
+            if head_ancestors.contains(&root) {
+                common_ancestor = Some((root, slot));
+                break;
+            }
+            candidate_roots.push(root);
+        }
+        let common_ancestor = common_ancestor.ok_or_else(|| {
+            warp_utils::reject::custom_server_error(
+                "head and candidate have no common ancestor".to_string(),
+            )
+        })?;
+
+        let orphaned_roots = proto_array
+            .core_proto_array()
+            .iter_block_roots(&head_root)
+            .map(|(root, _)| root)
+            .take_while(|root| *root != common_ancestor.0)
+            .collect::<Vec<_>>();
+
+        (
+            head_weight,
+            candidate_weight,
+            common_ancestor,
+            orphaned_roots,
+            candidate_roots,
+        )
+    };
+
+    let load_blocks = |roots: &[Hash256]| {
+        roots
+            .iter()
+            .map(|root| {
+                chain
+                    .get_block(root)
+                    .map_err(warp_utils::reject::beacon_chain_error)?
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "block {:?} is missing from the database",
+                            root
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<SignedBeaconBlock<T::EthSpec>>, _>>()
+    };
+    let orphaned_blocks = load_blocks(&orphaned_roots)?;
+    let candidate_blocks = load_blocks(&candidate_roots)?;
+
+    let candidate_bodies = candidate_blocks
+        .iter()
+        .map(|block| &block.message.body)
+        .collect::<Vec<_>>();
+    let candidate_attestations = candidate_bodies
+        .iter()
+        .flat_map(|body| body.attestations.iter())
+        .collect::<Vec<_>>();
+    let candidate_proposer_slashings = candidate_bodies
+        .iter()
+        .flat_map(|body| body.proposer_slashings.iter())
+        .collect::<Vec<_>>();
+    let candidate_attester_slashings = candidate_bodies
+        .iter()
+        .flat_map(|body| body.attester_slashings.iter())
+        .collect::<Vec<_>>();
+    let candidate_exits = candidate_bodies
+        .iter()
+        .flat_map(|body| body.voluntary_exits.iter())
+        .collect::<Vec<_>>();
+
+    let mut attestations_to_reinclude = 0;
+    let mut operations_to_reinclude = 0;
+    for body in orphaned_blocks.iter().map(|block| &block.message.body) {
+        attestations_to_reinclude += body
+            .attestations
+            .iter()
+            .filter(|att| !candidate_attestations.contains(att))
+            .count() as u64;
+        operations_to_reinclude += body
+            .proposer_slashings
+            .iter()
+            .filter(|slashing| !candidate_proposer_slashings.contains(slashing))
+            .count() as u64
+            + body
+                .attester_slashings
+                .iter()
+                .filter(|slashing| !candidate_attester_slashings.contains(slashing))
+                .count() as u64
+            + body
+                .voluntary_exits
+                .iter()
+                .filter(|exit| !candidate_exits.contains(exit))
+                .count() as u64;
+    }
+
+    let orphaned_root_set = orphaned_roots.iter().collect::<HashSet<_>>();
+    let pool_attestations = chain.op_pool.get_all_attestations();
+    let pool_attestations_for_orphaned_blocks = pool_attestations
+        .iter()
+        .filter(|att| orphaned_root_set.contains(&att.data.beacon_block_root))
+        .count() as u64;
+
+    let invalidated_pool_operations = pool_attestations
+        .iter()
+        .filter(|att| candidate_attestations.contains(att))
+        .count() as u64
+        + chain
+            .op_pool
+            .get_all_proposer_slashings()
+            .iter()
+            .filter(|slashing| {
+                candidate_proposer_slashings.iter().any(|included| {
+                    included.signed_header_1.message.proposer_index
+                        == slashing.signed_header_1.message.proposer_index
+                })
+            })
+            .count() as u64
+        + chain
+            .op_pool
+            .get_all_attester_slashings()
+            .iter()
+            .filter(|slashing| candidate_attester_slashings.contains(slashing))
+            .count() as u64
+        + chain
+            .op_pool
+            .get_all_voluntary_exits()
+            .iter()
+            .filter(|exit| {
+                candidate_exits.iter().any(|included| {
+                    included.message.validator_index == exit.message.validator_index
+                })
+            })
+            .count() as u64;
+
+    Ok(ReorgImpact {
+        head_root,
+        head_weight,
+        candidate_root,
+        candidate_weight,
+        common_ancestor_root: common_ancestor.0,
+        common_ancestor_slot: common_ancestor.1,
+        orphaned_blocks: orphaned_roots,
+        attestations_to_reinclude,
+        operations_to_reinclude,
+        pool_attestations_for_orphaned_blocks,
+        invalidated_pool_operations,
+    })
+}
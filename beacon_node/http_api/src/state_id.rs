@@ -1,3 +1,4 @@
+use crate::block_id::relative_slot;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2::types::StateId as CoreStateId;
 use std::str::FromStr;
@@ -41,6 +42,9 @@ impl StateId {
             }),
             CoreStateId::Slot(slot) => Ok(*slot),
             CoreStateId::Root(root) => return Ok(*root),
+            CoreStateId::Relative { anchor, offset } => {
+                return Self::slot(relative_slot(chain, *anchor, *offset)?).root(chain)
+            }
         }
         .map_err(warp_utils::reject::beacon_chain_error)?;
 
@@ -72,6 +76,9 @@ impl StateId {
                     .map_err(warp_utils::reject::beacon_chain_error)
             }
             CoreStateId::Slot(slot) => (self.root(chain)?, Some(*slot)),
+            CoreStateId::Relative { anchor, offset } => {
+                return Self::slot(relative_slot(chain, *anchor, *offset)?).state(chain)
+            }
             _ => (self.root(chain)?, None),
         };
 
@@ -0,0 +1,102 @@
+//! Tracks which validator client instance has most recently sent messages for each validator.
+//!
+//! Validator clients include a random identifier in the `VALIDATOR_CLIENT_INSTANCE_HEADER` of each
+//! request. If messages for the same validator and epoch arrive from two different instances it is
+//! very likely that the same keys are being run by two validator clients, which will eventually
+//! lead to a slashing.
+//!
+//! A validator client generates a new identifier each time it starts, so a different instance in
+//! a later epoch is expected after a restart and is not reported.
+use crate::metrics;
+use eth2::lighthouse::DuplicateValidatorClient;
+use parking_lot::RwLock;
+use slog::{warn, Logger};
+use std::collections::HashMap;
+use types::Epoch;
+
+/// The number of epochs prior to the current epoch for which observations are retained, since
+/// attestations from the previous epoch may still be received.
+const RETAINED_EPOCHS: u64 = 1;
+
+/// The maximum number of duplicates which are retained for the HTTP API.
+const MAX_DUPLICATES: usize = 1_024;
+
+#[derive(Default)]
+pub struct ValidatorClientInstances {
+    /// Maps a validator index to the last instance seen for it, and the epoch in which it was seen.
+    seen: RwLock<HashMap<u64, (String, Epoch)>>,
+    duplicates: RwLock<Vec<DuplicateValidatorClient>>,
+}
+
+impl ValidatorClientInstances {
+    /// Record that a message for `validator_index` in `epoch` was received from `instance`.
+    ///
+    /// Returns `true` if a message for the same validator and epoch was received from a different
+    /// instance.
+    pub fn observe(
+        &self,
+        validator_index: u64,
+        instance: &str,
+        epoch: Epoch,
+        log: &Logger,
+    ) -> bool {
+        let previous_instance = {
+            let mut seen = self.seen.write();
+            match seen.get(&validator_index) {
+                Some((previous_instance, previous_epoch)) if *previous_epoch == epoch => {
+                    if previous_instance == instance {
+                        return false;
+                    }
+                    previous_instance.clone()
+                }
+                // Messages from earlier epochs are ignored.
+                Some((_, previous_epoch)) if *previous_epoch > epoch => return false,
+                // A different instance in a later epoch is expected after a restart.
+                _ => {
+                    seen.insert(validator_index, (instance.to_string(), epoch));
+                    return false;
+                }
+            }
+        };
+
+        warn!(
+            log,
+            "Validator messages from multiple clients";
+            "msg" => "the same validator may be running in more than one validator client, \
+                      this may result in slashing",
+            "validator_index" => validator_index,
+            "epoch" => epoch,
+            "instance" => instance,
+            "previous_instance" => &previous_instance,
+        );
+        metrics::inc_counter(&metrics::HTTP_API_DUPLICATE_VALIDATOR_CLIENTS_TOTAL);
+
+        let mut duplicates = self.duplicates.write();
+        if duplicates.len() >= MAX_DUPLICATES {
+            duplicates.remove(0);
+        }
+        duplicates.push(DuplicateValidatorClient {
+            validator_index,
+            epoch,
+            instances: vec![previous_instance, instance.to_string()],
+        });
+
+        true
+    }
+
+    /// Remove all observations prior to `epoch`.
+    pub fn prune(&self, epoch: Epoch) {
+        let min_epoch = epoch.saturating_sub(RETAINED_EPOCHS);
+        self.seen
+            .write()
+            .retain(|_, (_, seen_epoch)| *seen_epoch >= min_epoch);
+        self.duplicates
+            .write()
+            .retain(|duplicate| duplicate.epoch >= min_epoch);
+    }
+
+    /// Returns all duplicates which have been observed and not yet pruned.
+    pub fn duplicates(&self) -> Vec<DuplicateValidatorClient> {
+        self.duplicates.read().clone()
+    }
+}
@@ -8,7 +8,7 @@ use discv5::enr::{CombinedKey, EnrBuilder};
 use environment::null_logger;
 use eth2::Error;
 use eth2::StatusCode;
-use eth2::{types::*, BeaconNodeHttpClient};
+use eth2::{reqwest, types::*, BeaconNodeHttpClient};
 use eth2_libp2p::{
     rpc::methods::MetaData,
     types::{EnrBitfield, SyncState},
@@ -355,6 +355,22 @@ impl ApiTester {
             StateId::Slot(Slot::from(SKIPPED_SLOTS[2])),
             StateId::Slot(Slot::from(SKIPPED_SLOTS[3])),
             StateId::Root(Hash256::zero()),
+            StateId::Relative {
+                anchor: RelativeAnchor::Head,
+                offset: -1,
+            },
+            StateId::Relative {
+                anchor: RelativeAnchor::Head,
+                offset: 1,
+            },
+            StateId::Relative {
+                anchor: RelativeAnchor::Genesis,
+                offset: 1,
+            },
+            StateId::Relative {
+                anchor: RelativeAnchor::Finalized,
+                offset: 1,
+            },
         ];
         ids.push(StateId::Root(self.chain.head_info().unwrap().state_root));
         ids
@@ -373,11 +389,44 @@ impl ApiTester {
             BlockId::Slot(Slot::from(SKIPPED_SLOTS[2])),
             BlockId::Slot(Slot::from(SKIPPED_SLOTS[3])),
             BlockId::Root(Hash256::zero()),
+            BlockId::Relative {
+                anchor: RelativeAnchor::Head,
+                offset: -1,
+            },
+            BlockId::Relative {
+                anchor: RelativeAnchor::Head,
+                offset: 1,
+            },
+            BlockId::Relative {
+                anchor: RelativeAnchor::Genesis,
+                offset: 1,
+            },
+            BlockId::Relative {
+                anchor: RelativeAnchor::Justified,
+                offset: 1,
+            },
         ];
         ids.push(BlockId::Root(self.chain.head_info().unwrap().block_root));
         ids
     }
 
+    fn relative_slot(&self, anchor: RelativeAnchor, offset: i64) -> Slot {
+        let head = self.chain.head_info().unwrap();
+        let anchor_slot = match anchor {
+            RelativeAnchor::Head => head.slot,
+            RelativeAnchor::Genesis => self.chain.spec.genesis_slot,
+            RelativeAnchor::Finalized => head
+                .finalized_checkpoint
+                .epoch
+                .start_slot(E::slots_per_epoch()),
+            RelativeAnchor::Justified => head
+                .current_justified_checkpoint
+                .epoch
+                .start_slot(E::slots_per_epoch()),
+        };
+        Slot::new((anchor_slot.as_u64() as i64 + offset) as u64)
+    }
+
     fn get_state(&self, state_id: StateId) -> Option<BeaconState<E>> {
         match state_id {
             StateId::Head => Some(self.chain.head().unwrap().beacon_state),
@@ -425,6 +474,13 @@ impl ApiTester {
                 self.chain.get_state(&root, Some(slot)).unwrap()
             }
             StateId::Root(root) => self.chain.get_state(&root, None).unwrap(),
+            StateId::Relative { anchor, offset } => {
+                let slot = self.relative_slot(anchor, offset);
+                self.chain
+                    .state_root_at_slot(slot)
+                    .unwrap()
+                    .and_then(|root| self.chain.get_state(&root, Some(slot)).unwrap())
+            }
         }
     }
 
@@ -479,6 +535,10 @@ impl ApiTester {
                 }
                 StateId::Slot(slot) => self.chain.state_root_at_slot(slot).unwrap(),
                 StateId::Root(root) => Some(root),
+                StateId::Relative { anchor, offset } => self
+                    .chain
+                    .state_root_at_slot(self.relative_slot(anchor, offset))
+                    .unwrap(),
             };
 
             assert_eq!(result, expected, "{:?}", state_id);
@@ -796,6 +856,9 @@ impl ApiTester {
                 .block_root_at_slot(slot, WhenSlotSkipped::None)
                 .unwrap(),
             BlockId::Root(root) => Some(root),
+            BlockId::Relative { anchor, offset } => {
+                self.get_block_root(BlockId::Slot(self.relative_slot(anchor, offset)))
+            }
         }
     }
 
@@ -1051,6 +1114,69 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_beacon_pool_attestations_duplicate_validator_client(mut self) -> Self {
+        let client_with_instance = |instance: &str| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(
+                VALIDATOR_CLIENT_INSTANCE_HEADER,
+                reqwest::header::HeaderValue::from_str(instance).unwrap(),
+            );
+            BeaconNodeHttpClient::from_components(
+                SensitiveUrl::parse(self.client.as_ref()).unwrap(),
+                reqwest::Client::builder()
+                    .default_headers(headers)
+                    .build()
+                    .unwrap(),
+            )
+        };
+
+        let first = client_with_instance("first");
+        let second = client_with_instance("second");
+
+        first
+            .post_beacon_pool_attestations(self.attestations.as_slice())
+            .await
+            .unwrap();
+
+        assert!(
+            self.network_rx.recv().await.is_some(),
+            "valid attestation should be sent to network"
+        );
+
+        assert!(
+            self.client
+                .get_lighthouse_duplicate_validator_clients()
+                .await
+                .unwrap()
+                .data
+                .is_empty(),
+            "no duplicates should be reported for a single instance"
+        );
+
+        // The attestations have already been seen, so the second instance should be rejected.
+        second
+            .post_beacon_pool_attestations(self.attestations.as_slice())
+            .await
+            .unwrap_err();
+
+        let duplicates = self
+            .client
+            .get_lighthouse_duplicate_validator_clients()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(duplicates.len(), self.attestations.len());
+        for duplicate in duplicates {
+            assert_eq!(
+                duplicate.instances,
+                vec!["first".to_string(), "second".to_string()]
+            );
+        }
+
+        self
+    }
+
     pub async fn test_post_beacon_pool_attestations_invalid(mut self) -> Self {
         let mut attestations = Vec::new();
         for attestation in &self.attestations {
@@ -1104,6 +1230,98 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_beacon_pool_attestations_filtered(mut self) -> Self {
+        self.client
+            .post_beacon_pool_attestations(self.attestations.as_slice())
+            .await
+            .unwrap();
+        assert!(self.network_rx.recv().await.is_some());
+
+        let mut all = self.chain.op_pool.get_all_attestations();
+        all.extend(self.chain.naive_aggregation_pool.read().iter().cloned());
+        assert!(!all.is_empty(), "precondition: pool has attestations");
+
+        let slot = self.attestations[0].data.slot;
+        let committee_index = self.attestations[0].data.index;
+
+        let queries = vec![
+            AttestationPoolQuery::default(),
+            AttestationPoolQuery {
+                committee_index: Some(committee_index),
+                ..AttestationPoolQuery::default()
+            },
+            AttestationPoolQuery {
+                from_slot: Some(slot),
+                to_slot: Some(slot),
+                ..AttestationPoolQuery::default()
+            },
+            AttestationPoolQuery {
+                from_slot: Some(slot + 1),
+                ..AttestationPoolQuery::default()
+            },
+            AttestationPoolQuery {
+                to_slot: Some(slot - 1),
+                ..AttestationPoolQuery::default()
+            },
+        ];
+
+        for query in queries {
+            let expected = all
+                .iter()
+                .filter(|attestation| query.matches(&attestation.data))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let json = self
+                .client
+                .get_beacon_pool_attestations_with_query::<E>(&query)
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(json, expected, "{:?}", query);
+
+            let ssz = self
+                .client
+                .get_beacon_pool_attestations_ssz::<E>(&query)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(ssz, expected, "{:?}", query);
+        }
+
+        // Attestations from the current slot are not yet within the inclusion window.
+        let current_slot = self.chain.slot().unwrap();
+        let expected = all
+            .iter()
+            .filter(|attestation| {
+                let slot = attestation.data.slot;
+                slot + self.chain.spec.min_attestation_inclusion_delay <= current_slot
+                    && current_slot <= slot + E::slots_per_epoch()
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        assert!(
+            all.iter()
+                .any(|attestation| attestation.data.slot == current_slot),
+            "precondition: pool has attestations from the current slot"
+        );
+        let only_includable = self
+            .client
+            .get_beacon_pool_attestations_with_query::<E>(&AttestationPoolQuery {
+                only_includable: Some(true),
+                ..AttestationPoolQuery::default()
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(only_includable, expected);
+        assert!(only_includable
+            .iter()
+            .all(|attestation| attestation.data.slot < current_slot));
+
+        self
+    }
+
     pub async fn test_post_beacon_pool_attester_slashings_valid(mut self) -> Self {
         self.client
             .post_beacon_pool_attester_slashings(&self.attester_slashing)
@@ -2047,6 +2265,135 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_disk_space(self) -> Self {
+        let result = self.client.get_lighthouse_disk_space().await.unwrap().data;
+
+        let expected = eth2::lighthouse::DiskSpaceStatus {
+            free_bytes: self.chain.disk_space.free_bytes(),
+            threshold_bytes: self.chain.config.disk_space_threshold,
+            protective_mode: false,
+        };
+
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_reorg_impact(self) -> Self {
+        let head = self.chain.head_info().unwrap();
+
+        // Switching to the current head should have no impact.
+        let result = self
+            .client
+            .get_lighthouse_analysis_reorg_impact(head.block_root)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result.head_root, head.block_root);
+        assert_eq!(result.candidate_root, head.block_root);
+        assert_eq!(result.head_weight, result.candidate_weight);
+        assert_eq!(result.common_ancestor_root, head.block_root);
+        assert_eq!(result.common_ancestor_slot, head.slot);
+        assert!(result.orphaned_blocks.is_empty());
+        assert_eq!(result.attestations_to_reinclude, 0);
+        assert_eq!(result.operations_to_reinclude, 0);
+        assert_eq!(result.invalidated_pool_operations, 0);
+
+        // Switching to the parent should orphan the head.
+        let parent_root = self
+            .chain
+            .fork_choice
+            .read()
+            .get_block(&head.block_root)
+            .unwrap()
+            .parent_root
+            .unwrap();
+        let result = self
+            .client
+            .get_lighthouse_analysis_reorg_impact(parent_root)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result.common_ancestor_root, parent_root);
+        assert_eq!(result.orphaned_blocks, vec![head.block_root]);
+        assert!(result.candidate_weight >= result.head_weight);
+
+        // Unknown blocks should be rejected.
+        let error = self
+            .client
+            .get_lighthouse_analysis_reorg_impact(Hash256::repeat_byte(42))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_sync_lookups(mut self) -> Self {
+        let client = &self.client;
+        let network_rx = &mut self.network_rx;
+
+        // The lookups reported by the sync manager are returned.
+        let (result, ()) = tokio::join!(client.get_lighthouse_sync_lookups(), async {
+            match network_rx.recv().await {
+                Some(NetworkMessage::GetBlockLookups(sender)) => sender.send(vec![]).unwrap(),
+                _ => panic!("should request the block lookups"),
+            }
+        });
+        assert!(result.unwrap().data.is_empty());
+
+        // A sync manager which does not respond results in a 503 rather than a hanging request.
+        let error = client.get_lighthouse_sync_lookups().await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(matches!(
+            network_rx.recv().await,
+            Some(NetworkMessage::GetBlockLookups(_))
+        ));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_ui_health_timeline(self) -> Self {
+        let current_slot = self.chain.slot().unwrap();
+
+        let timeline = self
+            .client
+            .get_lighthouse_ui_health_timeline(None)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(timeline.len() as u64, E::slots_per_epoch());
+        assert_eq!(timeline.last().unwrap().slot, current_slot);
+
+        for slot_health in &timeline {
+            let expected = self
+                .chain
+                .block_root_at_slot(slot_health.slot, WhenSlotSkipped::None)
+                .unwrap();
+            assert_eq!(
+                slot_health.block_root, expected,
+                "slot {}",
+                slot_health.slot
+            );
+            assert!(!slot_health.reorged);
+            if let Some(participation) = slot_health.attestation_participation {
+                assert!((0.0..=1.0).contains(&participation));
+            }
+        }
+
+        let error = self
+            .client
+            .get_lighthouse_ui_health_timeline(Some(0))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
     pub async fn test_get_lighthouse_syncing(self) -> Self {
         self.client.get_lighthouse_syncing().await.unwrap();
 
@@ -2122,6 +2469,120 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_beacon_states_summary(self) -> Self {
+        for state_id in self.interesting_state_ids() {
+            let json_result = self
+                .client
+                .get_lighthouse_beacon_states_summary(&state_id)
+                .await
+                .unwrap()
+                .map(|res| res.data);
+            let ssz_result = self
+                .client
+                .get_lighthouse_beacon_states_summary_ssz(&state_id)
+                .await
+                .unwrap();
+
+            let expected = self.get_state(state_id).map(|mut state| {
+                state
+                    .build_committee_cache(RelativeEpoch::Current, &self.chain.spec)
+                    .unwrap();
+                BeaconStateSummary::from_state(&state, &self.chain.spec).unwrap()
+            });
+
+            assert_eq!(json_result, expected, "{:?}", state_id);
+            assert_eq!(ssz_result, expected, "{:?}", state_id);
+        }
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_beacon_block_roots(self) -> Self {
+        let head = self.chain.head_info().unwrap();
+        let unknown_root = Hash256::repeat_byte(42);
+        let block_roots = vec![head.block_root, unknown_root, self.chain.genesis_block_root];
+
+        let result = self
+            .client
+            .post_lighthouse_beacon_block_roots(&block_roots)
+            .await
+            .unwrap()
+            .data;
+
+        // The unknown root is omitted.
+        assert_eq!(result.len(), 2);
+
+        assert_eq!(result[0].block_root, head.block_root);
+        assert_eq!(result[0].slot, head.slot);
+        assert_eq!(result[0].state_root, head.state_root);
+        assert!(result[0].canonical);
+        assert!(!result[0].finalized);
+
+        let genesis_block = self
+            .chain
+            .get_block(&self.chain.genesis_block_root)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result[1].block_root, self.chain.genesis_block_root);
+        assert_eq!(result[1].slot, genesis_block.slot());
+        assert_eq!(result[1].state_root, genesis_block.state_root());
+        assert!(result[1].canonical);
+        assert!(result[1].finalized);
+
+        // Too many roots are rejected.
+        let too_many = vec![head.block_root; 1025];
+        assert!(self
+            .client
+            .post_lighthouse_beacon_block_roots(&too_many)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_database_blocks(self) -> Self {
+        let head_slot = self.chain.head_info().unwrap().slot;
+
+        let blocks = self
+            .client
+            .get_lighthouse_database_blocks::<E>(Slot::new(0), head_slot)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let expected = (0..=head_slot.as_u64())
+            .map(Slot::new)
+            .filter_map(|slot| {
+                self.chain
+                    .block_root_at_slot(slot, WhenSlotSkipped::None)
+                    .unwrap()
+            })
+            .map(|root| self.chain.get_block(&root).unwrap().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(!expected.is_empty());
+        assert_eq!(blocks, expected);
+
+        // A range containing only the head block.
+        let blocks = self
+            .client
+            .get_lighthouse_database_blocks::<E>(head_slot, head_slot)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(blocks.as_slice(), &expected[expected.len() - 1..]);
+
+        // An inverted range is rejected.
+        let error = self
+            .client
+            .get_lighthouse_database_blocks::<E>(head_slot, head_slot - 1)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
     pub async fn test_get_lighthouse_staking(self) -> Self {
         let result = self.client.get_lighthouse_staking().await.unwrap();
 
@@ -2130,6 +2591,38 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_and_delete_lighthouse_validator_monitor_validators(self) -> Self {
+        let pubkeys = self
+            .validator_keypairs
+            .iter()
+            .take(2)
+            .map(|keypair| keypair.pk.compress())
+            .collect::<Vec<_>>();
+        let initial = self.chain.validator_monitor.read().num_validators();
+
+        self.client
+            .post_lighthouse_validator_monitor_validators(&pubkeys)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            self.chain.validator_monitor.read().num_validators(),
+            initial + pubkeys.len()
+        );
+
+        self.client
+            .delete_lighthouse_validator_monitor_validators(&pubkeys)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            self.chain.validator_monitor.read().num_validators(),
+            initial
+        );
+
+        self
+    }
+
     pub async fn test_get_events(self) -> Self {
         // Subscribe to all events
         let topics = vec![
@@ -2367,6 +2860,20 @@ async fn beacon_pools_post_attestations_valid() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_pools_get_attestations_filtered() {
+    ApiTester::new()
+        .test_get_beacon_pool_attestations_filtered()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_pools_post_attestations_duplicate_validator_client() {
+    ApiTester::new()
+        .test_post_beacon_pool_attestations_duplicate_validator_client()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn beacon_pools_post_attestations_invalid() {
     ApiTester::new()
@@ -2585,6 +3092,22 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_beacon_states_ssz()
         .await
+        .test_get_lighthouse_beacon_states_summary()
+        .await
         .test_get_lighthouse_staking()
+        .await
+        .test_get_lighthouse_disk_space()
+        .await
+        .test_get_lighthouse_analysis_reorg_impact()
+        .await
+        .test_get_lighthouse_sync_lookups()
+        .await
+        .test_get_lighthouse_ui_health_timeline()
+        .await
+        .test_post_lighthouse_beacon_block_roots()
+        .await
+        .test_get_lighthouse_database_blocks()
+        .await
+        .test_post_and_delete_lighthouse_validator_monitor_validators()
         .await;
 }
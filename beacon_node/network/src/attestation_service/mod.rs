@@ -181,6 +181,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         }
     }
 
+    /// Return the number of validators which have recently subscribed via this node.
+    pub fn known_validator_count(&self) -> usize {
+        self.known_validators.len()
+    }
+
     /// Processes a list of validator subscriptions.
     ///
     /// This will:
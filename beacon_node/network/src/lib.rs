@@ -12,6 +12,7 @@ mod beacon_processor;
 mod metrics;
 mod nat;
 mod persisted_dht;
+mod persisted_peer_scores;
 mod router;
 mod status;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
@@ -0,0 +1,136 @@
+use eth2_libp2p::{PeerId, PersistableScore};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeerScores`. All zero because `PersistedPeerScores`
+/// has its own column.
+pub const PEER_SCORES_DB_KEY: Hash256 = Hash256::zero();
+
+/// Returns the persisted peer scores along with the time which has elapsed since they were
+/// persisted.
+pub fn load_peer_scores<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> (Vec<(PeerId, PersistableScore)>, Duration) {
+    match store.get_item::<PersistedPeerScores>(&PEER_SCORES_DB_KEY) {
+        Ok(Some(persisted)) => {
+            let elapsed = unix_time()
+                .checked_sub(Duration::from_secs(persisted.saved_at))
+                .unwrap_or_else(|| Duration::from_secs(0));
+            let scores = persisted
+                .scores
+                .into_iter()
+                .filter_map(|score| {
+                    let peer_id = PeerId::from_bytes(&score.peer_id).ok()?;
+                    Some((
+                        peer_id,
+                        PersistableScore {
+                            lighthouse_score: f64::from_bits(score.lighthouse_score),
+                            ban_remaining: Duration::from_secs(score.ban_remaining),
+                        },
+                    ))
+                })
+                .collect();
+            (scores, elapsed)
+        }
+        _ => (vec![], Duration::from_secs(0)),
+    }
+}
+
+/// Attempt to persist the peer scores to `store`.
+pub fn persist_peer_scores<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    scores: Vec<(PeerId, PersistableScore)>,
+) -> Result<(), store::Error> {
+    let scores = scores
+        .into_iter()
+        .map(|(peer_id, score)| PersistedPeerScore {
+            peer_id: peer_id.to_bytes(),
+            lighthouse_score: score.lighthouse_score.to_bits(),
+            ban_remaining: score.ban_remaining.as_secs(),
+        })
+        .collect();
+
+    store.put_item(
+        &PEER_SCORES_DB_KEY,
+        &PersistedPeerScores {
+            saved_at: unix_time().as_secs(),
+            scores,
+        },
+    )
+}
+
+fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+}
+
+/// The score of a single peer, in a form suitable for SSZ encoding.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedPeerScore {
+    /// The protobuf encoding of the `PeerId`.
+    pub peer_id: Vec<u8>,
+    /// The bits of the `f64` lighthouse score.
+    pub lighthouse_score: u64,
+    /// The number of seconds remaining before a banned peer's score begins to decay.
+    pub ban_remaining: u64,
+}
+
+/// Wrapper around the peer scores for persistence to disk.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedPeerScores {
+    /// The UNIX timestamp (in seconds) at which the scores were persisted.
+    pub saved_at: u64,
+    pub scores: Vec<PersistedPeerScore>,
+}
+
+impl StoreItem for PersistedPeerScores {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerScores
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::{HotColdDB, MemoryStore};
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_peer_scores() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+
+        let scores = vec![(
+            PeerId::random(),
+            PersistableScore {
+                lighthouse_score: -42.5,
+                ban_remaining: Duration::from_secs(60),
+            },
+        )];
+        persist_peer_scores(store.clone(), scores.clone()).unwrap();
+
+        let (loaded, elapsed) = load_peer_scores(store);
+        assert_eq!(loaded, scores);
+        assert!(elapsed < Duration::from_secs(60));
+    }
+}
@@ -11,14 +11,14 @@ use crate::error;
 use crate::service::NetworkMessage;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2_libp2p::{
-    rpc::RequestId, MessageId, NetworkGlobals, PeerId, PeerRequestId, PubsubMessage, Request,
-    Response,
+    rpc::RequestId, types::BlockLookupInfo, LookupConfig, MessageId, NetworkGlobals, PeerId,
+    PeerRequestId, PubsubMessage, Request, Response,
 };
 use futures::prelude::*;
 use processor::Processor;
 use slog::{debug, o, trace};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use types::EthSpec;
 
@@ -66,6 +66,8 @@ pub enum RouterMessage<T: EthSpec> {
     PubsubMessage(MessageId, PeerId, PubsubMessage<T>, bool),
     /// The peer manager has requested we re-status a peer.
     StatusPeer(PeerId),
+    /// A snapshot of the block lookups in progress has been requested.
+    GetBlockLookups(oneshot::Sender<Vec<BlockLookupInfo>>),
 }
 
 impl<T: BeaconChainTypes> Router<T> {
@@ -75,6 +77,7 @@ impl<T: BeaconChainTypes> Router<T> {
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
         executor: task_executor::TaskExecutor,
+        lookup_config: LookupConfig,
         log: slog::Logger,
     ) -> error::Result<mpsc::UnboundedSender<RouterMessage<T::EthSpec>>> {
         let message_handler_log = log.new(o!("service"=> "router"));
@@ -88,6 +91,7 @@ impl<T: BeaconChainTypes> Router<T> {
             beacon_chain,
             network_globals.clone(),
             network_send,
+            lookup_config,
             &log,
         );
 
@@ -147,6 +151,9 @@ impl<T: BeaconChainTypes> Router<T> {
             RouterMessage::PubsubMessage(id, peer_id, gossip, should_process) => {
                 self.handle_gossip(id, peer_id, gossip, should_process);
             }
+            RouterMessage::GetBlockLookups(sender) => {
+                self.processor.get_block_lookups(sender);
+            }
         }
     }
 
@@ -5,12 +5,15 @@ use crate::service::NetworkMessage;
 use crate::sync::SyncMessage;
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2_libp2p::rpc::*;
-use eth2_libp2p::{MessageId, NetworkGlobals, PeerId, PeerRequestId, Request, Response};
+use eth2_libp2p::{
+    types::BlockLookupInfo, LookupConfig, MessageId, NetworkGlobals, PeerId, PeerRequestId,
+    Request, Response,
+};
 use slog::{debug, error, o, trace, warn};
 use std::cmp;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use types::{
     Attestation, AttesterSlashing, ChainSpec, EthSpec, ProposerSlashing, SignedAggregateAndProof,
     SignedBeaconBlock, SignedVoluntaryExit, SubnetId,
@@ -38,6 +41,7 @@ impl<T: BeaconChainTypes> Processor<T> {
         beacon_chain: Arc<BeaconChain<T>>,
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
+        lookup_config: LookupConfig,
         log: &slog::Logger,
     ) -> Self {
         let sync_logger = log.new(o!("service"=> "sync"));
@@ -51,6 +55,7 @@ impl<T: BeaconChainTypes> Processor<T> {
             network_globals.clone(),
             network_send.clone(),
             beacon_processor_send.clone(),
+            lookup_config,
             sync_logger,
         );
 
@@ -101,6 +106,11 @@ impl<T: BeaconChainTypes> Processor<T> {
         }
     }
 
+    /// Requests a snapshot of the block lookups in progress from the sync manager.
+    pub fn get_block_lookups(&mut self, sender: oneshot::Sender<Vec<BlockLookupInfo>>) {
+        self.send_to_sync(SyncMessage::GetBlockLookups(sender));
+    }
+
     /// Sends a `Status` message to the peer.
     ///
     /// Called when we first connect to a peer, or when the PeerManager determines we need to
@@ -1,4 +1,5 @@
 use crate::persisted_dht::{load_dht, persist_dht};
+use crate::persisted_peer_scores::{load_peer_scores, persist_peer_scores};
 use crate::router::{Router, RouterMessage};
 use crate::{
     attestation_service::{AttServiceMessage, AttestationService},
@@ -10,14 +11,17 @@ use eth2_libp2p::{
     rpc::{GoodbyeReason, RPCResponseErrorCode, RequestId},
     Libp2pEvent, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request, Response,
 };
-use eth2_libp2p::{types::GossipKind, BehaviourEvent, MessageId, NetworkGlobals, PeerId};
+use eth2_libp2p::{
+    types::{BlockLookupInfo, GossipKind},
+    BehaviourEvent, MessageId, NetworkGlobals, PeerId,
+};
 use eth2_libp2p::{MessageAcceptance, Service as LibP2PService};
 use futures::prelude::*;
 use slog::{debug, error, info, o, trace, warn};
 use std::{net::SocketAddr, sync::Arc, time::Duration};
 use store::HotColdDB;
 use task_executor::ShutdownReason;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::Sleep;
 use types::{EthSpec, RelativeEpoch, SubnetId, Unsigned, ValidatorSubscription};
 
@@ -87,6 +91,8 @@ pub enum NetworkMessage<T: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Request a snapshot of the block lookups currently in progress in the sync manager.
+    GetBlockLookups(oneshot::Sender<Vec<BlockLookupInfo>>),
 }
 
 /// Service that handles communication between internal services and the `eth2_libp2p` network service.
@@ -182,6 +188,23 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the scores of peers from the previous run, applying decay for the time elapsed
+        // since they were persisted.
+        let (scores_to_load, elapsed) =
+            load_peer_scores::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone());
+        debug!(
+            network_log,
+            "Restoring peer scores";
+            "peers" => scores_to_load.len(),
+            "elapsed_secs" => elapsed.as_secs(),
+        );
+        {
+            let mut peer_db = network_globals.peers.write();
+            for (peer_id, score) in scores_to_load {
+                peer_db.restore_score(peer_id, score, elapsed);
+            }
+        }
+
         // launch derived network services
 
         // router task
@@ -190,6 +213,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             network_globals.clone(),
             network_send.clone(),
             executor.clone(),
+            config.parent_lookup.clone(),
             network_log.clone(),
         )?;
 
@@ -259,6 +283,11 @@ fn spawn_service<T: BeaconChainTypes>(
 
                 }
                 _ = service.gossipsub_parameter_update.tick() => {
+                    // inform the peer manager of the local validator count, which may influence
+                    // the target peer count
+                    let local_validators = service.attestation_service.known_validator_count();
+                    service.libp2p.swarm.peer_manager().set_local_validator_count(local_validators);
+
                     if let Ok(slot) = service.beacon_chain.slot() {
                         if let Some(active_validators) = service.beacon_chain.with_head(|head| {
                                 Ok::<_, BeaconChainError>(
@@ -363,6 +392,14 @@ fn spawn_service<T: BeaconChainTypes>(
                         }
                         NetworkMessage::ReportPeer { peer_id, action, source } => service.libp2p.report_peer(&peer_id, action, source),
                         NetworkMessage::GoodbyePeer { peer_id, reason, source } => service.libp2p.goodbye_peer(&peer_id, reason, source),
+                        NetworkMessage::GetBlockLookups(sender) => {
+                            let _ = service
+                                .router_send
+                                .send(RouterMessage::GetBlockLookups(sender))
+                                .map_err(|_| {
+                                    debug!(service.log, "Failed to send block lookup request to router");
+                                });
+                        }
                         NetworkMessage::Subscribe { subscriptions } => {
                             if let Err(e) = service
                                 .attestation_service
@@ -584,6 +621,22 @@ impl<T: BeaconChainTypes> Drop for NetworkService<T> {
             ),
         }
 
+        let scores = self.network_globals.peers.read().persistable_scores();
+        debug!(
+            self.log,
+            "Persisting peer scores to store";
+            "Number of peers" => scores.len(),
+        );
+        if let Err(e) =
+            persist_peer_scores::<T::EthSpec, T::HotStore, T::ColdStore>(self.store.clone(), scores)
+        {
+            error!(
+                self.log,
+                "Failed to persist peer scores on drop";
+                "error" => ?e
+            );
+        }
+
         // attempt to remove port mappings
         crate::nat::remove_mappings(self.upnp_mappings.0, self.upnp_mappings.1, &self.log);
 
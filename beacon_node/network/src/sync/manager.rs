@@ -24,14 +24,18 @@
 //!
 //! When a block with an unknown parent is received and we are in `Regular` sync mode, the block is
 //! queued for lookup. A round-robin approach is used to request the parent from the known list of
-//! fully sync'd peers. If `PARENT_FAIL_TOLERANCE` attempts at requesting the block fails, we
-//! drop the propagated block and downvote the peer that sent it to us.
+//! fully sync'd peers. If too many attempts at downloading the block fail, or too many incorrect
+//! blocks are returned (see `LookupConfig`), we drop the propagated block and downvote the peer
+//! that sent it to us. Failed requests may be retried after a delay which increases with each
+//! failure.
 //!
 //! Block Lookup
 //!
 //! To keep the logic maintained to the syncing thread (and manage the request_ids), when a block
 //! needs to be searched for (i.e if an attestation references an unknown block) this manager can
-//! search for the block and subsequently search for parents if needed.
+//! search for the block and subsequently search for parents if needed. If a peer fails to return
+//! the block (including when the request times out), the search is retried with another synced
+//! peer, up to `SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS` times.
 
 use super::network_context::SyncNetworkContext;
 use super::peer_sync_info::{remote_sync_type, PeerSyncType};
@@ -42,18 +46,25 @@ use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockError};
 use eth2_libp2p::rpc::{methods::MAX_REQUEST_BLOCKS, BlocksByRootRequest, GoodbyeReason};
-use eth2_libp2p::types::{NetworkGlobals, SyncState};
+use eth2_libp2p::types::{
+    BlockLookupInfo, BlockLookupKind, BlockLookupState, NetworkGlobals, SyncState,
+};
 use eth2_libp2p::SyncInfo;
-use eth2_libp2p::{PeerAction, PeerId};
+use eth2_libp2p::{LookupConfig, PeerAction, PeerId};
 use fnv::FnvHashMap;
+use futures::StreamExt;
 use lru_cache::LRUCache;
 use slog::{crit, debug, error, info, trace, warn, Logger};
 use smallvec::SmallVec;
 use ssz_types::VariableList;
 use std::boxed::Box;
+use std::collections::HashSet;
 use std::ops::Sub;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::time::DelayQueue;
 use types::{Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 /// The number of slots ahead of us that is allowed before requesting a long-range (batch)  Sync
@@ -64,12 +75,21 @@ use types::{Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 /// gossip if no peers are further than this range ahead of us that we have not already downloaded
 /// blocks for.
 pub const SLOT_IMPORT_TOLERANCE: usize = 32;
-/// How many attempts we try to find a parent of a block before we give up trying .
-const PARENT_FAIL_TOLERANCE: usize = 5;
 /// The maximum depth we will search for a parent block. In principle we should have sync'd any
 /// canonical chain to its head once the peer connects. A chain should not appear where it's depth
 /// is further back than the most recent head slot.
 const PARENT_DEPTH_TOLERANCE: usize = SLOT_IMPORT_TOLERANCE * 2;
+/// The number of times a single block lookup may fail to download the block before the lookup is
+/// abandoned. This is independent of the parent lookup limits in `LookupConfig`.
+const SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS: usize = 3;
+/// The maximum number of block lookups which may wait for space in the beacon processor queue.
+const MAX_PARKED_LOOKUPS: usize = 16;
+/// The number of times a block lookup may find the beacon processor queue full before the lookup
+/// is abandoned.
+const MAX_PARKED_LOOKUP_ATTEMPTS: usize = 5;
+/// The delay before re-sending a parked block to the beacon processor. The delay is multiplied by
+/// the number of attempts so far.
+const PARKED_LOOKUP_RETRY_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug)]
 /// A message than can be sent to the sync manager thread.
@@ -118,6 +138,9 @@ pub enum SyncMessage<T: EthSpec> {
         /// The peer that instigated the chain lookup.
         peer_id: PeerId,
     },
+
+    /// Request a snapshot of all block lookups currently in progress.
+    GetBlockLookups(oneshot::Sender<Vec<BlockLookupInfo>>),
 }
 
 /// The result of processing a multiple blocks (a chain segment).
@@ -138,6 +161,10 @@ struct ParentRequests<T: EthSpec> {
     /// lookup is failed and rejected.
     failed_attempts: usize,
 
+    /// The number of times a peer returned a block which was not the requested parent. If too
+    /// many occur, this lookup is failed and rejected.
+    failed_processing_attempts: usize,
+
     /// The peer who last submitted a block. If the chain ends or fails, this is the peer that is
     /// penalized.
     last_submitted_peer: PeerId,
@@ -146,6 +173,35 @@ struct ParentRequests<T: EthSpec> {
     pending: Option<RequestId>,
 }
 
+/// A block lookup whose block has been downloaded but could not be sent for processing because the
+/// beacon processor queue was full. Rather than re-downloading the block, the lookup is parked and
+/// its block is re-sent after a delay.
+struct ParkedLookup<T: EthSpec> {
+    lookup: ParkedLookupKind<T>,
+    /// The number of times the beacon processor queue has been full for this lookup.
+    attempts: usize,
+}
+
+enum ParkedLookupKind<T: EthSpec> {
+    /// A single block lookup and the peer which sent the block.
+    Single {
+        peer_id: PeerId,
+        block: SignedBeaconBlock<T>,
+    },
+    /// A parent lookup, whose last downloaded block is yet to be processed.
+    Parent(ParentRequests<T>),
+}
+
+/// The outcome of sending a block to the beacon processor.
+enum BlockProcessingOutcome<T: EthSpec> {
+    /// The block was processed.
+    Processed(Result<Hash256, BlockError<T>>),
+    /// The beacon processor queue was full so the block was not processed.
+    QueueFull,
+    /// The block could not be processed for some other reason.
+    Dropped,
+}
+
 /// The primary object for handling and driving all the current syncing logic. It maintains the
 /// current state of the syncing process, the number of useful peers, downloaded blocks and
 /// controls the logic behind both the long-range (batch) sync and the on-going potential parent
@@ -172,12 +228,26 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// A cache of failed chain lookups to prevent duplicate searches.
     failed_chains: LRUCache<Hash256>,
 
+    /// Controls the number of attempts and the delay between attempts for parent lookups.
+    lookup_config: LookupConfig,
+
+    /// Parent lookups which are waiting to be retried, keyed by the root of the first block in
+    /// the lookup. The lookups themselves remain in `parent_queue` without a pending request.
+    delayed_parent_lookups: DelayQueue<Hash256>,
+
     /// A collection of block hashes being searched for and a flag indicating if a result has been
     /// received or not.
     ///
     /// The flag allows us to determine if the peer returned data or sent us nothing.
     single_block_lookups: FnvHashMap<RequestId, SingleBlockRequest>,
 
+    /// Block lookups which are waiting for space in the beacon processor queue, keyed by the root
+    /// of the block to be processed.
+    parked_lookups: FnvHashMap<Hash256, ParkedLookup<T::EthSpec>>,
+
+    /// The roots of parked lookups, which are retried once their delay expires.
+    delayed_parked_lookups: DelayQueue<Hash256>,
+
     /// A multi-threaded, non-blocking processor for applying messages to the beacon chain.
     beacon_processor_send: mpsc::Sender<BeaconWorkEvent<T>>,
 
@@ -191,13 +261,21 @@ struct SingleBlockRequest {
     pub hash: Hash256,
     /// Whether a block was received from this request, or the peer returned an empty response.
     pub block_returned: bool,
+    /// The number of failed attempts to download the block.
+    pub failed_attempts: usize,
+    /// The peers which have been asked for the block.
+    pub used_peers: HashSet<PeerId>,
 }
 
 impl SingleBlockRequest {
-    pub fn new(hash: Hash256) -> Self {
+    pub fn new(hash: Hash256, peer_id: PeerId) -> Self {
+        let mut used_peers = HashSet::new();
+        used_peers.insert(peer_id);
         Self {
             hash,
             block_returned: false,
+            failed_attempts: 0,
+            used_peers,
         }
     }
 }
@@ -211,6 +289,7 @@ pub fn spawn<T: BeaconChainTypes>(
     network_globals: Arc<NetworkGlobals<T::EthSpec>>,
     network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
     beacon_processor_send: mpsc::Sender<BeaconWorkEvent<T>>,
+    lookup_config: LookupConfig,
     log: slog::Logger,
 ) -> mpsc::UnboundedSender<SyncMessage<T::EthSpec>> {
     assert!(
@@ -233,7 +312,11 @@ pub fn spawn<T: BeaconChainTypes>(
         input_channel: sync_recv,
         parent_queue: SmallVec::new(),
         failed_chains: LRUCache::new(500),
+        lookup_config,
+        delayed_parent_lookups: DelayQueue::new(),
         single_block_lookups: FnvHashMap::default(),
+        parked_lookups: FnvHashMap::default(),
+        delayed_parked_lookups: DelayQueue::new(),
         beacon_processor_send,
         log: log.clone(),
     };
@@ -347,7 +430,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 // add the block to response
                 parent_request.downloaded_blocks.push(block);
                 // queue for processing
-                self.process_parent_request(parent_request).await;
+                self.process_parent_request(parent_request, 0).await;
             }
             None => {
                 // this is a stream termination
@@ -361,6 +444,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         warn!(self.log, "Peer didn't respond with a block it referenced"; "referenced_block_hash" => %single_block_request.hash, "peer_id" =>  %peer_id);
                         self.network
                             .report_peer(peer_id, PeerAction::MidToleranceError);
+                        self.retry_single_block_lookup(single_block_request);
                     }
                     return;
                 }
@@ -391,29 +475,77 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     async fn process_block_async(
         &mut self,
         block: SignedBeaconBlock<T::EthSpec>,
-    ) -> Option<Result<Hash256, BlockError<T::EthSpec>>> {
+    ) -> BlockProcessingOutcome<T::EthSpec> {
         let (event, rx) = BeaconWorkEvent::rpc_beacon_block(Box::new(block));
         match self.beacon_processor_send.try_send(event) {
             Ok(_) => {}
+            Err(TrySendError::Full(_)) => return BlockProcessingOutcome::QueueFull,
             Err(e) => {
                 error!(
                     self.log,
                     "Failed to send sync block to processor";
                     "error" => ?e
                 );
-                return None;
+                return BlockProcessingOutcome::Dropped;
             }
         }
 
         match rx.await {
-            Ok(block_result) => Some(block_result),
+            Ok(block_result) => BlockProcessingOutcome::Processed(block_result),
             Err(_) => {
                 warn!(
                     self.log,
                     "Sync block not processed";
                     "msg" => "likely due to system resource exhaustion"
                 );
-                None
+                BlockProcessingOutcome::Dropped
+            }
+        }
+    }
+
+    /// Parks a block lookup which could not be processed because the beacon processor queue was
+    /// full, so that it can be retried after a delay. The lookup is abandoned if too many lookups
+    /// are parked or it has already been parked too many times.
+    fn park_lookup(&mut self, block_root: Hash256, lookup: ParkedLookup<T::EthSpec>) {
+        if lookup.attempts >= MAX_PARKED_LOOKUP_ATTEMPTS
+            || self.parked_lookups.len() >= MAX_PARKED_LOOKUPS
+        {
+            warn!(
+                self.log,
+                "Dropping block lookup, beacon processor is busy";
+                "block" => %block_root,
+                "attempts" => lookup.attempts,
+                "parked_lookups" => self.parked_lookups.len(),
+            );
+            return;
+        }
+
+        debug!(
+            self.log,
+            "Beacon processor is busy, delaying block lookup";
+            "block" => %block_root,
+            "attempts" => lookup.attempts,
+        );
+        self.delayed_parked_lookups.insert(
+            block_root,
+            PARKED_LOOKUP_RETRY_DELAY * lookup.attempts as u32,
+        );
+        self.parked_lookups.insert(block_root, lookup);
+    }
+
+    /// Re-sends the block of a parked lookup to the beacon processor once its delay has expired.
+    async fn retry_parked_lookup(&mut self, block_root: Hash256) {
+        let ParkedLookup { lookup, attempts } = match self.parked_lookups.remove(&block_root) {
+            Some(parked) => parked,
+            None => return,
+        };
+
+        match lookup {
+            ParkedLookupKind::Single { peer_id, block } => {
+                self.process_single_block(peer_id, block, attempts).await
+            }
+            ParkedLookupKind::Parent(parent_request) => {
+                self.process_parent_request(parent_request, attempts).await
             }
         }
     }
@@ -436,9 +568,29 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             return;
         }
 
+        self.process_single_block(peer_id, block, 0).await;
+    }
+
+    /// Processes the block of a single block lookup. `busy_attempts` is the number of times the
+    /// beacon processor queue has previously been full for this lookup.
+    async fn process_single_block(
+        &mut self,
+        peer_id: PeerId,
+        block: SignedBeaconBlock<T::EthSpec>,
+        busy_attempts: usize,
+    ) {
         let block_result = match self.process_block_async(block.clone()).await {
-            Some(block_result) => block_result,
-            None => return,
+            BlockProcessingOutcome::Processed(block_result) => block_result,
+            BlockProcessingOutcome::QueueFull => {
+                let block_root = block.canonical_root();
+                let lookup = ParkedLookup {
+                    lookup: ParkedLookupKind::Single { peer_id, block },
+                    attempts: busy_attempts + 1,
+                };
+                self.park_lookup(block_root, lookup);
+                return;
+            }
+            BlockProcessingOutcome::Dropped => return,
         };
 
         // we have the correct block, try and process it
@@ -524,12 +676,20 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 return;
             }
         }
+        for parked in self.parked_lookups.values() {
+            if let ParkedLookupKind::Parent(parent_req) = &parked.lookup {
+                if parent_req.downloaded_blocks.contains(&block) {
+                    return;
+                }
+            }
+        }
 
         debug!(self.log, "Unknown block received. Starting a parent lookup"; "block_slot" => block.message.slot, "block_hash" => %block.canonical_root());
 
         let parent_request = ParentRequests {
             downloaded_blocks: vec![block],
             failed_attempts: 0,
+            failed_processing_attempts: 0,
             last_submitted_peer: peer_id,
             pending: None,
         };
@@ -545,11 +705,12 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             return;
         }
 
-        // Do not re-request a block that is already being requested
+        // Do not re-request a block that is already being requested or is waiting to be processed
         if self
             .single_block_lookups
             .values()
             .any(|single_block_request| single_block_request.hash == block_hash)
+            || self.parked_lookups.contains_key(&block_hash)
         {
             return;
         }
@@ -561,21 +722,81 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             "block" => %block_hash
         );
 
+        self.send_single_block_request(peer_id, SingleBlockRequest::new(block_hash, peer_id));
+    }
+
+    /// Retries a failed single block lookup with a synced peer which has not yet been asked for the
+    /// block. The lookup is abandoned if there are too many failures or no peers remain.
+    fn retry_single_block_lookup(&mut self, mut single_block_request: SingleBlockRequest) {
+        single_block_request.failed_attempts += 1;
+
+        if single_block_request.failed_attempts >= SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS {
+            debug!(
+                self.log,
+                "Single block lookup failed";
+                "block" => %single_block_request.hash,
+                "reason" => "too many failed attempts",
+            );
+            return;
+        }
+
+        let next_peer = self
+            .network_globals
+            .peers
+            .read()
+            .synced_peers()
+            .find(|peer_id| !single_block_request.used_peers.contains(*peer_id))
+            .cloned();
+
+        let peer_id = if let Some(peer_id) = next_peer {
+            peer_id
+        } else {
+            debug!(
+                self.log,
+                "Single block lookup failed";
+                "block" => %single_block_request.hash,
+                "reason" => "no remaining peers",
+            );
+            return;
+        };
+
+        debug!(
+            self.log,
+            "Retrying block search with another peer";
+            "peer_id" => %peer_id,
+            "block" => %single_block_request.hash,
+            "failed_attempts" => single_block_request.failed_attempts,
+        );
+
+        single_block_request.block_returned = false;
+        single_block_request.used_peers.insert(peer_id);
+        self.send_single_block_request(peer_id, single_block_request);
+    }
+
+    /// Sends a `BlocksByRoot` request for the block in `single_block_request` to `peer_id`.
+    fn send_single_block_request(
+        &mut self,
+        peer_id: PeerId,
+        single_block_request: SingleBlockRequest,
+    ) {
         let request = BlocksByRootRequest {
-            block_roots: VariableList::from(vec![block_hash]),
+            block_roots: VariableList::from(vec![single_block_request.hash]),
         };
 
         if let Ok(request_id) = self.network.blocks_by_root_request(peer_id, request) {
             self.single_block_lookups
-                .insert(request_id, SingleBlockRequest::new(block_hash));
+                .insert(request_id, single_block_request);
         }
     }
 
     fn inject_error(&mut self, peer_id: PeerId, request_id: RequestId) {
         trace!(self.log, "Sync manager received a failed RPC");
-        // remove any single block lookups
-        if self.single_block_lookups.remove(&request_id).is_some() {
-            // this was a single block request lookup, look no further
+        // RPC errors include requests which timed out. Retry single block lookups with another
+        // peer, unless the block has already been received.
+        if let Some(single_block_request) = self.single_block_lookups.remove(&request_id) {
+            if !single_block_request.block_returned {
+                self.retry_single_block_lookup(single_block_request);
+            }
             return;
         }
 
@@ -696,7 +917,14 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     // manager
 
     /// A new block has been received for a parent lookup query, process it.
-    async fn process_parent_request(&mut self, mut parent_request: ParentRequests<T::EthSpec>) {
+    ///
+    /// `busy_attempts` is the number of times the beacon processor queue has previously been full
+    /// for this lookup.
+    async fn process_parent_request(
+        &mut self,
+        mut parent_request: ParentRequests<T::EthSpec>,
+        busy_attempts: usize,
+    ) {
         // verify the last added block is the parent of the last requested block
 
         if parent_request.downloaded_blocks.len() < 2 {
@@ -720,6 +948,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             // The sent block is not the correct block, remove the head block and downvote
             // the peer
             let _ = parent_request.downloaded_blocks.pop();
+            parent_request.failed_processing_attempts += 1;
             let peer = parent_request.last_submitted_peer;
 
             warn!(self.log, "Peer sent invalid parent.";
@@ -753,8 +982,18 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 .expect("There is always at least one block in the queue");
 
             let block_result = match self.process_block_async(newest_block.clone()).await {
-                Some(block_result) => block_result,
-                None => return,
+                BlockProcessingOutcome::Processed(block_result) => block_result,
+                BlockProcessingOutcome::QueueFull => {
+                    // Keep the downloaded block so that it is processed when the lookup is retried.
+                    parent_request.downloaded_blocks.push(newest_block);
+                    let lookup = ParkedLookup {
+                        lookup: ParkedLookupKind::Parent(parent_request),
+                        attempts: busy_attempts + 1,
+                    };
+                    self.park_lookup(block_hash, lookup);
+                    return;
+                }
+                BlockProcessingOutcome::Dropped => return,
             };
 
             match block_result {
@@ -814,15 +1053,24 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     /// This checks to ensure there a peers to progress the query, checks for failures and
     /// initiates requests.
     fn request_parent(&mut self, mut parent_request: ParentRequests<T::EthSpec>) {
+        let too_many_download_attempts =
+            parent_request.failed_attempts >= self.lookup_config.max_download_attempts;
+        let too_many_processing_attempts =
+            parent_request.failed_processing_attempts >= self.lookup_config.max_processing_attempts;
+
         // check to make sure this request hasn't failed
-        if parent_request.failed_attempts >= PARENT_FAIL_TOLERANCE
+        if too_many_download_attempts
+            || too_many_processing_attempts
             || parent_request.downloaded_blocks.len() >= PARENT_DEPTH_TOLERANCE
         {
-            let error = if parent_request.failed_attempts >= PARENT_FAIL_TOLERANCE {
+            let error = if too_many_download_attempts {
                 // This is a peer-specific error and the chain could be continued with another
                 // peer. We don't consider this chain a failure and prevent retries with another
                 // peer.
                 "too many failed attempts"
+            } else if too_many_processing_attempts {
+                // As above, the peer is at fault rather than the chain.
+                "too many invalid parents"
             } else {
                 if !parent_request.downloaded_blocks.is_empty() {
                     self.failed_chains
@@ -846,6 +1094,40 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             return; // drop the request
         }
 
+        // Back off before retrying a request which has previously failed.
+        let failures = parent_request.failed_attempts + parent_request.failed_processing_attempts;
+        let delay = self.lookup_config.retry_delay * failures as u32;
+        if delay > Duration::from_secs(0) {
+            if let Some(block) = parent_request.downloaded_blocks.first() {
+                self.delayed_parent_lookups
+                    .insert(block.canonical_root(), delay);
+                // The previous request has failed, so responses to it must no longer match this
+                // lookup.
+                parent_request.pending = None;
+                self.parent_queue.push(parent_request);
+            }
+            return;
+        }
+
+        self.send_parent_request(parent_request);
+    }
+
+    /// Retries a parent lookup once its delay has expired.
+    fn retry_parent_request(&mut self, chain_hash: Hash256) {
+        if let Some(pos) = self.parent_queue.iter().position(|request| {
+            request.pending.is_none()
+                && request
+                    .downloaded_blocks
+                    .first()
+                    .map_or(false, |block| block.canonical_root() == chain_hash)
+        }) {
+            let parent_request = self.parent_queue.remove(pos);
+            self.send_parent_request(parent_request);
+        }
+    }
+
+    /// Sends a request for the parent of the last downloaded block in `parent_request`.
+    fn send_parent_request(&mut self, mut parent_request: ParentRequests<T::EthSpec>) {
         let parent_hash = if let Some(block) = parent_request.downloaded_blocks.last() {
             block.parent_root()
         } else {
@@ -868,73 +1150,505 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    /// Returns a snapshot of all single block and parent lookups currently in progress.
+    fn block_lookups(&self) -> Vec<BlockLookupInfo> {
+        let single_lookups = self
+            .single_block_lookups
+            .values()
+            .map(|request| BlockLookupInfo {
+                kind: BlockLookupKind::Single,
+                block_root: request.hash,
+                state: if request.block_returned {
+                    BlockLookupState::Processing
+                } else {
+                    BlockLookupState::Downloading
+                },
+                downloaded_blocks: request.block_returned as usize,
+                failed_download_attempts: request.failed_attempts,
+                failed_processing_attempts: 0,
+                peers: request
+                    .used_peers
+                    .iter()
+                    .map(|peer_id| peer_id.to_string())
+                    .collect(),
+            });
+
+        let parent_lookups = self.parent_queue.iter().filter_map(|request| {
+            Some(BlockLookupInfo {
+                kind: BlockLookupKind::Parent,
+                block_root: request.downloaded_blocks.first()?.canonical_root(),
+                state: if request.pending.is_some() {
+                    BlockLookupState::Downloading
+                } else {
+                    BlockLookupState::AwaitingRetry
+                },
+                downloaded_blocks: request.downloaded_blocks.len(),
+                failed_download_attempts: request.failed_attempts,
+                failed_processing_attempts: request.failed_processing_attempts,
+                peers: vec![request.last_submitted_peer.to_string()],
+            })
+        });
+
+        let parked_lookups =
+            self.parked_lookups
+                .iter()
+                .map(|(block_root, parked)| match &parked.lookup {
+                    ParkedLookupKind::Single { peer_id, .. } => BlockLookupInfo {
+                        kind: BlockLookupKind::Single,
+                        block_root: *block_root,
+                        state: BlockLookupState::Processing,
+                        downloaded_blocks: 1,
+                        failed_download_attempts: 0,
+                        failed_processing_attempts: 0,
+                        peers: vec![peer_id.to_string()],
+                    },
+                    ParkedLookupKind::Parent(request) => BlockLookupInfo {
+                        kind: BlockLookupKind::Parent,
+                        block_root: request
+                            .downloaded_blocks
+                            .first()
+                            .map_or(*block_root, |block| block.canonical_root()),
+                        state: BlockLookupState::Processing,
+                        downloaded_blocks: request.downloaded_blocks.len(),
+                        failed_download_attempts: request.failed_attempts,
+                        failed_processing_attempts: request.failed_processing_attempts,
+                        peers: vec![request.last_submitted_peer.to_string()],
+                    },
+                });
+
+        single_lookups
+            .chain(parent_lookups)
+            .chain(parked_lookups)
+            .collect()
+    }
+
     /// The main driving future for the sync manager.
     async fn main(&mut self) {
         // process any inbound messages
         loop {
-            if let Some(sync_message) = self.input_channel.recv().await {
-                match sync_message {
-                    SyncMessage::AddPeer(peer_id, info) => {
-                        self.add_peer(peer_id, info);
-                    }
-                    SyncMessage::BlocksByRangeResponse {
-                        peer_id,
-                        request_id,
-                        beacon_block,
-                    } => {
-                        self.range_sync.blocks_by_range_response(
-                            &mut self.network,
-                            peer_id,
-                            request_id,
-                            beacon_block.map(|b| *b),
-                        );
-                        self.update_sync_state();
-                    }
-                    SyncMessage::BlocksByRootResponse {
-                        peer_id,
-                        request_id,
-                        beacon_block,
-                    } => {
-                        self.blocks_by_root_response(peer_id, request_id, beacon_block.map(|b| *b))
-                            .await;
-                    }
-                    SyncMessage::UnknownBlock(peer_id, block) => {
-                        self.add_unknown_block(peer_id, *block);
-                    }
-                    SyncMessage::UnknownBlockHash(peer_id, block_hash) => {
-                        self.search_for_block(peer_id, block_hash);
-                    }
-                    SyncMessage::Disconnect(peer_id) => {
-                        self.peer_disconnect(&peer_id);
-                    }
-                    SyncMessage::RPCError(peer_id, request_id) => {
-                        self.inject_error(peer_id, request_id);
-                    }
-                    SyncMessage::BatchProcessed {
-                        chain_id,
-                        epoch,
-                        result,
-                    } => {
-                        self.range_sync.handle_block_process_result(
-                            &mut self.network,
-                            chain_id,
-                            epoch,
-                            result,
-                        );
-                        self.update_sync_state();
-                    }
-                    SyncMessage::ParentLookupFailed {
-                        chain_head,
-                        peer_id,
-                    } => {
-                        // A peer sent an object (block or attestation) that referenced a parent.
-                        // The processing of this chain failed.
-                        self.failed_chains.insert(chain_head);
-                        self.network
-                            .report_peer(peer_id, PeerAction::MidToleranceError);
-                    }
+            tokio::select! {
+                Some(sync_message) = self.input_channel.recv() => {
+                    self.handle_message(sync_message).await;
+                }
+                Some(Ok(expired)) = self.delayed_parent_lookups.next() => {
+                    self.retry_parent_request(expired.into_inner());
+                }
+                Some(Ok(expired)) = self.delayed_parked_lookups.next() => {
+                    self.retry_parked_lookup(expired.into_inner()).await;
+                }
+                else => return,
+            }
+        }
+    }
+
+    /// Handles a single message sent to the sync manager.
+    async fn handle_message(&mut self, sync_message: SyncMessage<T::EthSpec>) {
+        match sync_message {
+            SyncMessage::AddPeer(peer_id, info) => {
+                self.add_peer(peer_id, info);
+            }
+            SyncMessage::BlocksByRangeResponse {
+                peer_id,
+                request_id,
+                beacon_block,
+            } => {
+                self.range_sync.blocks_by_range_response(
+                    &mut self.network,
+                    peer_id,
+                    request_id,
+                    beacon_block.map(|b| *b),
+                );
+                self.update_sync_state();
+            }
+            SyncMessage::BlocksByRootResponse {
+                peer_id,
+                request_id,
+                beacon_block,
+            } => {
+                self.blocks_by_root_response(peer_id, request_id, beacon_block.map(|b| *b))
+                    .await;
+            }
+            SyncMessage::UnknownBlock(peer_id, block) => {
+                self.add_unknown_block(peer_id, *block);
+            }
+            SyncMessage::UnknownBlockHash(peer_id, block_hash) => {
+                self.search_for_block(peer_id, block_hash);
+            }
+            SyncMessage::Disconnect(peer_id) => {
+                self.peer_disconnect(&peer_id);
+            }
+            SyncMessage::RPCError(peer_id, request_id) => {
+                self.inject_error(peer_id, request_id);
+            }
+            SyncMessage::BatchProcessed {
+                chain_id,
+                epoch,
+                result,
+            } => {
+                self.range_sync.handle_block_process_result(
+                    &mut self.network,
+                    chain_id,
+                    epoch,
+                    result,
+                );
+                self.update_sync_state();
+            }
+            SyncMessage::ParentLookupFailed {
+                chain_head,
+                peer_id,
+            } => {
+                // A peer sent an object (block or attestation) that referenced a parent.
+                // The processing of this chain failed.
+                self.failed_chains.insert(chain_head);
+                self.network
+                    .report_peer(peer_id, PeerAction::MidToleranceError);
+            }
+            SyncMessage::GetBlockLookups(sender) => {
+                // The receiver may have been dropped if the HTTP request was cancelled.
+                let _ = sender.send(self.block_lookups());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+    use discv5::enr::{CombinedKey, EnrBuilder};
+    use environment::null_logger;
+    use eth2_libp2p::rpc::{methods::MetaData, RequestId as RpcRequestId};
+    use eth2_libp2p::types::EnrBitfield;
+    use eth2_libp2p::{PeerSyncStatus, Request};
+    use futures::FutureExt;
+    use types::{
+        test_utils::generate_deterministic_keypairs, BeaconBlock, MinimalEthSpec, Signature,
+    };
+
+    type E = MinimalEthSpec;
+    type T = EphemeralHarnessType<E>;
+
+    /// Provides a `SyncManager` whose outgoing network messages can be inspected.
+    struct TestRig {
+        sync_manager: SyncManager<T>,
+        network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
+        beacon_processor_rx: mpsc::Receiver<BeaconWorkEvent<T>>,
+        peer_id: PeerId,
+    }
+
+    impl TestRig {
+        fn new(lookup_config: LookupConfig) -> Self {
+            let harness =
+                BeaconChainHarness::new(MinimalEthSpec, generate_deterministic_keypairs(8));
+            let log = null_logger().unwrap();
+
+            let meta_data = MetaData {
+                seq_number: 0,
+                attnets: EnrBitfield::<E>::default(),
+            };
+            let enr_key = CombinedKey::generate_secp256k1();
+            let enr = EnrBuilder::new("v4").build(&enr_key).unwrap();
+            let network_globals = Arc::new(NetworkGlobals::new(
+                enr,
+                9000,
+                9000,
+                meta_data,
+                vec![],
+                &log,
+            ));
+
+            let chain = Arc::new(harness.chain);
+            let (network_tx, network_rx) = mpsc::unbounded_channel();
+            let (beacon_processor_tx, beacon_processor_rx) = mpsc::channel(1);
+            let (_sync_tx, sync_rx) = mpsc::unbounded_channel();
+
+            let sync_manager = SyncManager {
+                range_sync: RangeSync::new(chain.clone(), beacon_processor_tx.clone(), log.clone()),
+                network: SyncNetworkContext::new(network_tx, network_globals.clone(), log.clone()),
+                chain,
+                network_globals,
+                input_channel: sync_rx,
+                parent_queue: SmallVec::new(),
+                failed_chains: LRUCache::new(500),
+                lookup_config,
+                delayed_parent_lookups: DelayQueue::new(),
+                single_block_lookups: FnvHashMap::default(),
+                parked_lookups: FnvHashMap::default(),
+                delayed_parked_lookups: DelayQueue::new(),
+                beacon_processor_send: beacon_processor_tx,
+                log,
+            };
+
+            Self {
+                sync_manager,
+                network_rx,
+                beacon_processor_rx,
+                peer_id: PeerId::random(),
+            }
+        }
+
+        /// Returns an unsigned block at `slot` with the given parent. The block is never valid,
+        /// which is fine since it is only used to drive lookups.
+        fn block(&self, slot: u64, parent_root: Hash256) -> SignedBeaconBlock<E> {
+            let mut block = BeaconBlock::empty(&E::default_spec());
+            block.slot = Slot::new(slot);
+            block.parent_root = parent_root;
+            SignedBeaconBlock {
+                message: block,
+                signature: Signature::empty(),
+            }
+        }
+
+        /// Returns the request id and the requested root of the next `BlocksByRoot` request sent
+        /// to the network, skipping any other network messages.
+        fn next_block_request(&mut self) -> Option<(RequestId, Hash256)> {
+            while let Some(Some(message)) = self.network_rx.recv().now_or_never() {
+                if let NetworkMessage::SendRequest {
+                    request_id: RpcRequestId::Sync(request_id),
+                    request: Request::BlocksByRoot(request),
+                    ..
+                } = message
+                {
+                    return Some((request_id, request.block_roots[0]));
                 }
             }
+            None
         }
+
+        fn expect_block_request(&mut self) -> (RequestId, Hash256) {
+            self.next_block_request()
+                .expect("a BlocksByRoot request should have been sent")
+        }
+
+        fn expect_no_block_request(&mut self) {
+            assert!(
+                self.next_block_request().is_none(),
+                "no BlocksByRoot request should have been sent"
+            );
+        }
+
+        /// Occupies the only slot in the beacon processor queue, so that sending a block for
+        /// processing finds the queue full.
+        fn fill_beacon_processor_queue(&mut self) {
+            let (event, _) =
+                BeaconWorkEvent::rpc_beacon_block(Box::new(self.block(1, Hash256::zero())));
+            assert!(self
+                .sync_manager
+                .beacon_processor_send
+                .try_send(event)
+                .is_ok());
+        }
+
+        /// Adds a connected peer which is synced, so that failed lookups can be retried with it.
+        fn add_synced_peer(&self) -> PeerId {
+            let peer_id = PeerId::random();
+            let mut peers = self.sync_manager.network_globals.peers.write();
+            peers.connect_ingoing(&peer_id, "/ip4/0.0.0.0/tcp/9000".parse().unwrap(), None);
+            peers.peer_info_mut(&peer_id).unwrap().sync_status = PeerSyncStatus::Synced {
+                info: SyncInfo {
+                    head_slot: Slot::new(0),
+                    head_root: Hash256::zero(),
+                    finalized_epoch: Epoch::new(0),
+                    finalized_root: Hash256::zero(),
+                },
+            };
+            peer_id
+        }
+
+        fn parked_attempts(&self, block_root: Hash256) -> Option<usize> {
+            self.sync_manager
+                .parked_lookups
+                .get(&block_root)
+                .map(|parked| parked.attempts)
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_parent_request_is_retried_after_delay() {
+        let mut rig = TestRig::new(LookupConfig {
+            retry_delay: Duration::from_millis(10),
+            ..LookupConfig::default()
+        });
+        let peer_id = rig.peer_id;
+        let parent_root = Hash256::repeat_byte(1);
+        let block = rig.block(2, parent_root);
+        let chain_hash = block.canonical_root();
+
+        rig.sync_manager.add_unknown_block(peer_id, block);
+        let (first_request_id, requested_root) = rig.expect_block_request();
+        assert_eq!(requested_root, parent_root);
+
+        // The request fails, so the lookup waits before requesting the parent again.
+        rig.sync_manager.inject_error(peer_id, first_request_id);
+        rig.expect_no_block_request();
+        assert_eq!(rig.sync_manager.parent_queue.len(), 1);
+        assert_eq!(rig.sync_manager.parent_queue[0].pending, None);
+        assert_eq!(rig.sync_manager.parent_queue[0].failed_attempts, 1);
+
+        // A late stream termination for the failed request must not count as another failure.
+        rig.sync_manager
+            .blocks_by_root_response(peer_id, first_request_id, None)
+            .await;
+        assert_eq!(rig.sync_manager.parent_queue[0].failed_attempts, 1);
+        assert_eq!(rig.sync_manager.delayed_parent_lookups.len(), 1);
+
+        let expired = rig
+            .sync_manager
+            .delayed_parent_lookups
+            .next()
+            .await
+            .expect("the lookup should be delayed")
+            .expect("the delay queue should not error");
+        assert_eq!(expired.into_inner(), chain_hash);
+
+        rig.sync_manager.retry_parent_request(chain_hash);
+        let (retry_request_id, requested_root) = rig.expect_block_request();
+        assert_ne!(retry_request_id, first_request_id);
+        assert_eq!(requested_root, parent_root);
+        assert_eq!(rig.sync_manager.parent_queue.len(), 1);
+        assert_eq!(
+            rig.sync_manager.parent_queue[0].pending,
+            Some(retry_request_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn busy_block_lookup_is_parked() {
+        let mut rig = TestRig::new(LookupConfig::default());
+        let peer_id = rig.peer_id;
+        let block = rig.block(2, Hash256::repeat_byte(1));
+        let block_root = block.canonical_root();
+
+        rig.fill_beacon_processor_queue();
+        rig.sync_manager
+            .process_single_block(peer_id, block, 0)
+            .await;
+
+        assert_eq!(rig.parked_attempts(block_root), Some(1));
+        assert_eq!(rig.sync_manager.delayed_parked_lookups.len(), 1);
+        // The block is re-sent later rather than downloaded again.
+        rig.expect_no_block_request();
+    }
+
+    #[tokio::test]
+    async fn parked_lookup_is_retried_once_queue_has_space() {
+        let mut rig = TestRig::new(LookupConfig::default());
+        let peer_id = rig.peer_id;
+        let block = rig.block(2, Hash256::repeat_byte(1));
+        let block_root = block.canonical_root();
+
+        rig.fill_beacon_processor_queue();
+        rig.sync_manager
+            .process_single_block(peer_id, block, 0)
+            .await;
+        assert_eq!(rig.parked_attempts(block_root), Some(1));
+
+        // Free up the queue and wait for the lookup's delay to expire.
+        assert!(rig.beacon_processor_rx.recv().now_or_never().is_some());
+        let expired = rig
+            .sync_manager
+            .delayed_parked_lookups
+            .next()
+            .await
+            .expect("the lookup should be delayed")
+            .expect("the delay queue should not error");
+        assert_eq!(expired.into_inner(), block_root);
+
+        // The retry must send the parked block to the beacon processor. Dropping the work event
+        // lets the retry complete without a result.
+        let TestRig {
+            sync_manager,
+            beacon_processor_rx,
+            ..
+        } = &mut rig;
+        let (_, received) = tokio::join!(sync_manager.retry_parked_lookup(block_root), async {
+            beacon_processor_rx.recv().await.is_some()
+        });
+        assert!(received, "the parked block should be sent for processing");
+        assert_eq!(rig.parked_attempts(block_root), None);
+        rig.expect_no_block_request();
+    }
+
+    #[tokio::test]
+    async fn parked_lookup_is_abandoned_after_attempt_limit() {
+        let mut rig = TestRig::new(LookupConfig::default());
+        let peer_id = rig.peer_id;
+        let block = rig.block(2, Hash256::repeat_byte(1));
+        let block_root = block.canonical_root();
+
+        rig.fill_beacon_processor_queue();
+        rig.sync_manager
+            .process_single_block(peer_id, block, 0)
+            .await;
+
+        // The queue stays full, so each retry parks the lookup again until it is abandoned.
+        for attempts in 1..MAX_PARKED_LOOKUP_ATTEMPTS {
+            assert_eq!(rig.parked_attempts(block_root), Some(attempts));
+            rig.sync_manager.retry_parked_lookup(block_root).await;
+        }
+        assert_eq!(rig.parked_attempts(block_root), None);
+        assert!(rig.sync_manager.parked_lookups.is_empty());
+    }
+
+    #[tokio::test]
+    async fn parked_parent_lookups_are_not_duplicated() {
+        let mut rig = TestRig::new(LookupConfig::default());
+        let peer_id = rig.peer_id;
+
+        // Park a single parent lookup, as if its blocks found the beacon processor busy.
+        let parked_block = rig.block(2, Hash256::repeat_byte(0xff));
+        let parked_root = parked_block.canonical_root();
+        rig.sync_manager.park_lookup(
+            parked_root,
+            ParkedLookup {
+                lookup: ParkedLookupKind::Parent(ParentRequests {
+                    downloaded_blocks: vec![parked_block.clone()],
+                    failed_attempts: 0,
+                    failed_processing_attempts: 0,
+                    last_submitted_peer: peer_id,
+                    pending: None,
+                }),
+                attempts: 1,
+            },
+        );
+
+        // The parked lookup is already searching for the block, so no new lookup is started.
+        rig.sync_manager.add_unknown_block(peer_id, parked_block);
+        rig.expect_no_block_request();
+        assert!(rig.sync_manager.parent_queue.is_empty());
+
+        // Parked lookups do not prevent lookups for other blocks.
+        let block = rig.block(2, Hash256::repeat_byte(0xfe));
+        rig.sync_manager.add_unknown_block(peer_id, block);
+        rig.expect_block_request();
+        assert_eq!(rig.sync_manager.parent_queue.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn single_block_lookup_not_retried_after_block_returned() {
+        let mut rig = TestRig::new(LookupConfig::default());
+        let peer_id = rig.peer_id;
+        // Two synced peers, so that a peer remains available for a second retry.
+        rig.add_synced_peer();
+        rig.add_synced_peer();
+        let block_root = Hash256::repeat_byte(1);
+
+        // A request which fails before returning the block is retried with the synced peer.
+        rig.sync_manager
+            .single_block_lookups
+            .insert(1, SingleBlockRequest::new(block_root, peer_id));
+        rig.sync_manager.inject_error(peer_id, 1);
+        let (retry_request_id, requested_root) = rig.expect_block_request();
+        assert_eq!(requested_root, block_root);
+
+        // The retried request errors after the block was received, so there is nothing to retry.
+        rig.sync_manager
+            .single_block_lookups
+            .get_mut(&retry_request_id)
+            .expect("the retry should be tracked")
+            .block_returned = true;
+        rig.sync_manager.inject_error(peer_id, retry_request_id);
+        rig.expect_no_block_request();
+        assert!(rig.sync_manager.single_block_lookups.is_empty());
     }
 }
@@ -31,10 +31,26 @@ pub struct BatchInfo<T: EthSpec> {
     failed_processing_attempts: Vec<Attempt>,
     /// The number of download retries this batch has undergone due to a failed request.
     failed_download_attempts: Vec<PeerId>,
+    /// Blocks received before a download was interrupted, grouped by the peer which sent them. The
+    /// next download resumes from the slot after the last of these blocks, rather than from the
+    /// start of the batch.
+    ///
+    /// The blocks remain attributed to the peer which sent them, so that only that peer is
+    /// penalised if they turn out to be invalid.
+    partial_blocks: Vec<PartialDownload<T>>,
     /// State of the batch.
     state: BatchState<T>,
 }
 
+/// The blocks sent by a single peer before its download of a batch was interrupted.
+struct PartialDownload<T: EthSpec> {
+    /// The peer which sent the blocks.
+    peer_id: PeerId,
+    /// The first slot requested from the peer.
+    start_slot: Slot,
+    blocks: Vec<SignedBeaconBlock<T>>,
+}
+
 /// Current state of a batch
 pub enum BatchState<T: EthSpec> {
     /// The batch has failed either downloading or processing, but can be requested again.
@@ -42,7 +58,10 @@ pub enum BatchState<T: EthSpec> {
     /// The batch is being downloaded.
     Downloading(PeerId, Vec<SignedBeaconBlock<T>>, RequestId),
     /// The batch has been completely downloaded and is ready for processing.
-    AwaitingProcessing(PeerId, Vec<SignedBeaconBlock<T>>),
+    ///
+    /// The last element holds each peer which sent blocks of the batch, with the first slot
+    /// requested from it. There is more than one peer only if interrupted downloads were resumed.
+    AwaitingProcessing(PeerId, Vec<SignedBeaconBlock<T>>, Vec<(PeerId, Slot)>),
     /// The batch is being processed.
     Processing(Attempt),
     /// The batch was successfully processed and is waiting to be validated.
@@ -90,6 +109,7 @@ impl<T: EthSpec> BatchInfo<T> {
             end_slot,
             failed_processing_attempts: Vec::new(),
             failed_download_attempts: Vec::new(),
+            partial_blocks: Vec::new(),
             state: BatchState::AwaitingDownload,
         }
     }
@@ -124,21 +144,39 @@ impl<T: EthSpec> BatchInfo<T> {
         match &self.state {
             BatchState::AwaitingDownload | BatchState::Failed => None,
             BatchState::Downloading(peer_id, _, _)
-            | BatchState::AwaitingProcessing(peer_id, _)
+            | BatchState::AwaitingProcessing(peer_id, _, _)
             | BatchState::Processing(Attempt { peer_id, .. })
             | BatchState::AwaitingValidation(Attempt { peer_id, .. }) => Some(&peer_id),
             BatchState::Poisoned => unreachable!("Poisoned batch"),
         }
     }
 
+    /// The first slot of the next download, which follows any blocks retained from an interrupted
+    /// download.
+    fn request_start_slot(&self) -> Slot {
+        self.partial_blocks
+            .last()
+            .and_then(|partial| partial.blocks.last())
+            .map_or(self.start_slot, |block| block.slot() + 1)
+    }
+
     pub fn to_blocks_by_range_request(&self) -> BlocksByRangeRequest {
+        let start_slot = self.request_start_slot();
         BlocksByRangeRequest {
-            start_slot: self.start_slot.into(),
-            count: self.end_slot.sub(self.start_slot).into(),
+            start_slot: start_slot.into(),
+            count: self.end_slot.sub(start_slot).into(),
             step: 1,
         }
     }
 
+    /// The number of blocks retained from interrupted downloads.
+    pub fn partial_blocks(&self) -> usize {
+        self.partial_blocks
+            .iter()
+            .map(|partial| partial.blocks.len())
+            .sum()
+    }
+
     pub fn state(&self) -> &BatchState<T> {
         &self.state
     }
@@ -178,9 +216,10 @@ impl<T: EthSpec> BatchInfo<T> {
                 if let Some(last_slot) = blocks.last().map(|b| b.slot()) {
                     // the batch is non-empty
                     let first_slot = blocks[0].slot();
+                    let request_start_slot = self.request_start_slot();
 
-                    let failed_range = if first_slot < self.start_slot {
-                        Some((self.start_slot, first_slot))
+                    let failed_range = if first_slot < request_start_slot {
+                        Some((request_start_slot, first_slot))
                     } else if self.end_slot < last_slot {
                         Some((self.end_slot, last_slot))
                     } else {
@@ -189,8 +228,10 @@ impl<T: EthSpec> BatchInfo<T> {
 
                     if let Some((expected, received)) = failed_range {
                         // this is a failed download, register the attempt and check if the batch
-                        // can be tried again
+                        // can be tried again. The partial blocks are also dropped, in case they
+                        // were sent by the same faulty peer.
                         self.failed_download_attempts.push(peer);
+                        self.partial_blocks.clear();
                         self.state = if self.failed_download_attempts.len()
                             >= MAX_BATCH_DOWNLOAD_ATTEMPTS as usize
                         {
@@ -204,8 +245,17 @@ impl<T: EthSpec> BatchInfo<T> {
                     }
                 }
 
-                let received = blocks.len();
-                self.state = BatchState::AwaitingProcessing(peer, blocks);
+                let request_start_slot = self.request_start_slot();
+                let mut all_blocks = Vec::with_capacity(self.partial_blocks() + blocks.len());
+                let mut segments = Vec::with_capacity(self.partial_blocks.len() + 1);
+                for partial in std::mem::take(&mut self.partial_blocks) {
+                    segments.push((partial.peer_id, partial.start_slot));
+                    all_blocks.extend(partial.blocks);
+                }
+                segments.push((peer, request_start_slot));
+                all_blocks.extend(blocks);
+                let received = all_blocks.len();
+                self.state = BatchState::AwaitingProcessing(peer, all_blocks, segments);
                 Ok(received)
             }
             BatchState::Poisoned => unreachable!("Poisoned batch"),
@@ -222,15 +272,17 @@ impl<T: EthSpec> BatchInfo<T> {
     #[must_use = "Batch may have failed"]
     pub fn download_failed(&mut self) -> Result<IsFailed, WrongState> {
         match self.state.poison() {
-            BatchState::Downloading(peer, _, _request_id) => {
+            BatchState::Downloading(peer, blocks, _request_id) => {
                 // register the attempt and check if the batch can be tried again
                 self.failed_download_attempts.push(peer);
                 self.state = if self.failed_download_attempts.len()
                     >= MAX_BATCH_DOWNLOAD_ATTEMPTS as usize
                 {
+                    self.partial_blocks.clear();
                     BatchState::Failed
                 } else {
-                    // drop the blocks
+                    // keep the blocks received so far so the next download can resume after them
+                    self.retain_partial_blocks(peer, blocks);
                     BatchState::AwaitingDownload
                 };
                 Ok(self.state.is_failed())
@@ -246,6 +298,35 @@ impl<T: EthSpec> BatchInfo<T> {
         }
     }
 
+    /// Retains `blocks` sent by `peer_id` before its download was interrupted, provided they are
+    /// sequential and leave part of the batch to be downloaded. Otherwise all partial blocks are
+    /// dropped and the next download starts from the beginning of the batch.
+    fn retain_partial_blocks(&mut self, peer_id: PeerId, blocks: Vec<SignedBeaconBlock<T>>) {
+        let request_start_slot = self.request_start_slot();
+        let is_sequential = blocks
+            .iter()
+            .try_fold(request_start_slot, |min_slot, block| {
+                if block.slot() >= min_slot {
+                    Some(block.slot() + 1)
+                } else {
+                    None
+                }
+            })
+            .map_or(false, |next_slot| next_slot < self.end_slot);
+
+        if is_sequential {
+            if !blocks.is_empty() {
+                self.partial_blocks.push(PartialDownload {
+                    peer_id,
+                    start_slot: request_start_slot,
+                    blocks,
+                });
+            }
+        } else {
+            self.partial_blocks.clear();
+        }
+    }
+
     pub fn start_downloading_from_peer(
         &mut self,
         peer: PeerId,
@@ -269,8 +350,8 @@ impl<T: EthSpec> BatchInfo<T> {
 
     pub fn start_processing(&mut self) -> Result<Vec<SignedBeaconBlock<T>>, WrongState> {
         match self.state.poison() {
-            BatchState::AwaitingProcessing(peer, blocks) => {
-                self.state = BatchState::Processing(Attempt::new(peer, &blocks));
+            BatchState::AwaitingProcessing(peer, blocks, segments) => {
+                self.state = BatchState::Processing(Attempt::new(peer, &blocks, &segments));
                 Ok(blocks)
             }
             BatchState::Poisoned => unreachable!("Poisoned batch"),
@@ -353,16 +434,96 @@ pub struct Attempt {
     pub peer_id: PeerId,
     /// The hash of the blocks of the attempt.
     pub hash: u64,
+    /// The blocks of the attempt, grouped by the peer which sent them. There is more than one
+    /// segment only if the attempt resumed interrupted downloads from other peers.
+    segments: Vec<AttemptSegment>,
+}
+
+/// The blocks of an `Attempt` which were sent by a single peer.
+#[derive(PartialEq, Debug)]
+struct AttemptSegment {
+    /// The peer which sent the blocks.
+    peer_id: PeerId,
+    /// The first slot requested from the peer.
+    start_slot: Slot,
+    /// The slot and hash of each block sent by the peer.
+    blocks: Vec<(Slot, u64)>,
 }
 
 impl Attempt {
+    /// Creates an attempt from the `blocks` of a batch.
+    ///
+    /// `segments` holds each peer which sent blocks of the batch, with the first slot requested
+    /// from it, in order. The last segment is that of `peer_id`, which completed the download.
     #[allow(clippy::ptr_arg)]
-    fn new<T: EthSpec>(peer_id: PeerId, blocks: &Vec<SignedBeaconBlock<T>>) -> Self {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        blocks.as_ssz_bytes().hash(&mut hasher);
-        let hash = hasher.finish();
-        Attempt { peer_id, hash }
+    fn new<T: EthSpec>(
+        peer_id: PeerId,
+        blocks: &Vec<SignedBeaconBlock<T>>,
+        segments: &[(PeerId, Slot)],
+    ) -> Self {
+        let mut segments = segments
+            .iter()
+            .map(|(peer_id, start_slot)| AttemptSegment {
+                peer_id: *peer_id,
+                start_slot: *start_slot,
+                blocks: vec![],
+            })
+            .collect::<Vec<_>>();
+
+        for block in blocks {
+            let slot = block.slot();
+            if let Some(segment) = segments
+                .iter_mut()
+                .rev()
+                .find(|segment| slot >= segment.start_slot)
+            {
+                segment.blocks.push((slot, hash_ssz(block)));
+            }
+        }
+
+        Attempt {
+            peer_id,
+            hash: hash_ssz(blocks),
+            segments,
+        }
     }
+
+    /// Returns the peers which sent blocks that differ from those of `valid`, an attempt of the
+    /// same batch which has been validated.
+    ///
+    /// If the blocks of every segment match, the peer which completed the attempt is returned.
+    pub fn faulty_peers(&self, valid: &Attempt) -> Vec<PeerId> {
+        let valid_blocks = valid
+            .segments
+            .iter()
+            .flat_map(|segment| segment.blocks.iter().copied())
+            .collect::<Vec<_>>();
+
+        let mut faulty_peers = vec![];
+        for (i, segment) in self.segments.iter().enumerate() {
+            let end_slot = self.segments.get(i + 1).map(|next| next.start_slot);
+            let expected_blocks = valid_blocks.iter().filter(|(slot, _)| {
+                *slot >= segment.start_slot && end_slot.map_or(true, |end| *slot < end)
+            });
+
+            if !expected_blocks.eq(segment.blocks.iter())
+                && !faulty_peers.contains(&segment.peer_id)
+            {
+                faulty_peers.push(segment.peer_id);
+            }
+        }
+
+        if faulty_peers.is_empty() {
+            faulty_peers.push(self.peer_id);
+        }
+        faulty_peers
+    }
+}
+
+fn hash_ssz<T: Encode>(item: &T) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    item.as_ssz_bytes().hash(&mut hasher);
+    hasher.finish()
 }
 
 impl<T: EthSpec> slog::KV for &mut BatchInfo<T> {
@@ -391,6 +552,7 @@ impl<T: EthSpec> slog::KV for BatchInfo<T> {
         )?;
         serializer.emit_usize("downloaded", self.failed_download_attempts.len())?;
         serializer.emit_usize("processed", self.failed_processing_attempts.len())?;
+        serializer.emit_usize("partial_blocks", self.partial_blocks.len())?;
         serializer.emit_arguments("state", &format_args!("{:?}", self.state))?;
         slog::Result::Ok(())
     }
@@ -409,7 +571,7 @@ impl<T: EthSpec> std::fmt::Debug for BatchState<T> {
             }) => write!(f, "AwaitingValidation({})", peer_id),
             BatchState::AwaitingDownload => f.write_str("AwaitingDownload"),
             BatchState::Failed => f.write_str("Failed"),
-            BatchState::AwaitingProcessing(ref peer, ref blocks) => {
+            BatchState::AwaitingProcessing(ref peer, ref blocks, _) => {
                 write!(f, "AwaitingProcessing({}, {} blocks)", peer, blocks.len())
             }
             BatchState::Downloading(peer, blocks, request_id) => write!(
@@ -423,3 +585,130 @@ impl<T: EthSpec> std::fmt::Debug for BatchState<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, MinimalEthSpec, Signature};
+
+    type E = MinimalEthSpec;
+
+    fn block(slot: Slot) -> SignedBeaconBlock<E> {
+        let mut message = BeaconBlock::empty(&E::default_spec());
+        message.slot = slot;
+        SignedBeaconBlock {
+            message,
+            signature: Signature::empty(),
+        }
+    }
+
+    #[test]
+    fn interrupted_download_resumes_from_last_block() {
+        let mut batch = BatchInfo::<E>::new(&Epoch::new(0), 2);
+        let start_slot = batch.start_slot;
+        let end_slot = batch.end_slot;
+
+        batch
+            .start_downloading_from_peer(PeerId::random(), 1)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(block(start_slot)).ok().unwrap();
+        batch.add_block(block(start_slot + 2)).ok().unwrap();
+        assert!(!batch.download_failed().ok().unwrap());
+
+        // The next download resumes after the received blocks.
+        assert_eq!(batch.partial_blocks(), 2);
+        let request = batch.to_blocks_by_range_request();
+        assert_eq!(request.start_slot, (start_slot + 3).as_u64());
+        assert_eq!(request.count, (end_slot - (start_slot + 3)).as_u64());
+
+        batch
+            .start_downloading_from_peer(PeerId::random(), 2)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(block(start_slot + 3)).ok().unwrap();
+        assert_eq!(batch.download_completed().ok(), Some(3));
+        assert_eq!(batch.partial_blocks(), 0);
+        assert_eq!(
+            batch
+                .start_processing()
+                .ok()
+                .unwrap()
+                .iter()
+                .map(|block| block.slot())
+                .collect::<Vec<_>>(),
+            vec![start_slot, start_slot + 2, start_slot + 3]
+        );
+    }
+
+    #[test]
+    fn out_of_range_resumed_download_drops_partial_blocks() {
+        let mut batch = BatchInfo::<E>::new(&Epoch::new(0), 2);
+        let start_slot = batch.start_slot;
+
+        batch
+            .start_downloading_from_peer(PeerId::random(), 1)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(block(start_slot + 1)).ok().unwrap();
+        assert!(!batch.download_failed().ok().unwrap());
+        assert_eq!(batch.partial_blocks(), 1);
+
+        // The resumed download returns a block prior to the requested range.
+        batch
+            .start_downloading_from_peer(PeerId::random(), 2)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(block(start_slot)).ok().unwrap();
+        assert!(batch.download_completed().is_err());
+
+        assert_eq!(batch.partial_blocks(), 0);
+        assert_eq!(
+            batch.to_blocks_by_range_request().start_slot,
+            start_slot.as_u64()
+        );
+    }
+
+    #[test]
+    fn invalid_partial_blocks_are_attributed_to_their_peer() {
+        let mut batch = BatchInfo::<E>::new(&Epoch::new(0), 2);
+        let start_slot = batch.start_slot;
+        let interrupted_peer = PeerId::random();
+        let completing_peer = PeerId::random();
+        let valid_peer = PeerId::random();
+
+        // The first peer sends an invalid block before its download is interrupted.
+        let mut invalid_block = block(start_slot);
+        invalid_block.message.proposer_index = 1;
+        batch
+            .start_downloading_from_peer(interrupted_peer, 1)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(invalid_block).ok().unwrap();
+        assert!(!batch.download_failed().ok().unwrap());
+
+        // Another peer completes the download, but the batch fails processing.
+        batch
+            .start_downloading_from_peer(completing_peer, 2)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(block(start_slot + 1)).ok().unwrap();
+        assert_eq!(batch.download_completed().ok(), Some(2));
+        batch.start_processing().ok().unwrap();
+        assert!(!batch.processing_completed(false).ok().unwrap());
+
+        // A third peer sends the valid batch.
+        batch
+            .start_downloading_from_peer(valid_peer, 3)
+            .unwrap_or_else(|_| panic!("batch should be awaiting download"));
+        batch.add_block(block(start_slot)).ok().unwrap();
+        batch.add_block(block(start_slot + 1)).ok().unwrap();
+        assert_eq!(batch.download_completed().ok(), Some(2));
+        batch.start_processing().ok().unwrap();
+        assert!(!batch.processing_completed(true).ok().unwrap());
+
+        let valid_attempt = match batch.state() {
+            BatchState::AwaitingValidation(attempt) => attempt,
+            other => panic!("batch should be awaiting validation, not {:?}", other),
+        };
+        // Only the peer which sent the invalid block is at fault.
+        assert_eq!(
+            batch.attempts()[0].faulty_peers(valid_attempt),
+            vec![interrupted_peer]
+        );
+    }
+}
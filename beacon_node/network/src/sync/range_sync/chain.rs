@@ -597,25 +597,29 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                     for attempt in batch.attempts() {
                         // The validated batch has been re-processed
                         if attempt.hash != processed_attempt.hash {
-                            // The re-downloaded version was different
-                            if processed_attempt.peer_id != attempt.peer_id {
-                                // A different peer sent the correct batch, the previous peer did not
-                                // We negatively score the original peer.
-                                let action = PeerAction::LowToleranceError;
-                                debug!(self.log, "Re-processed batch validated. Scoring original peer";
-                                    "batch_epoch" => id, "score_adjustment" => %action,
-                                    "original_peer" => %attempt.peer_id, "new_peer" => %processed_attempt.peer_id
-                                );
-                                network.report_peer(attempt.peer_id, action);
-                            } else {
-                                // The same peer corrected it's previous mistake. There was an error, so we
-                                // negative score the original peer.
-                                let action = PeerAction::MidToleranceError;
-                                debug!(self.log, "Re-processed batch validated by the same peer";
-                                    "batch_epoch" => id, "score_adjustment" => %action,
-                                    "original_peer" => %attempt.peer_id, "new_peer" => %processed_attempt.peer_id
-                                );
-                                network.report_peer(attempt.peer_id, action);
+                            // The re-downloaded version was different. Only the peers which sent
+                            // the differing blocks are scored, since an attempt may include blocks
+                            // from interrupted downloads by other peers.
+                            for original_peer in attempt.faulty_peers(processed_attempt) {
+                                if processed_attempt.peer_id != original_peer {
+                                    // A different peer sent the correct batch, the previous peer did not
+                                    // We negatively score the original peer.
+                                    let action = PeerAction::LowToleranceError;
+                                    debug!(self.log, "Re-processed batch validated. Scoring original peer";
+                                        "batch_epoch" => id, "score_adjustment" => %action,
+                                        "original_peer" => %original_peer, "new_peer" => %processed_attempt.peer_id
+                                    );
+                                    network.report_peer(original_peer, action);
+                                } else {
+                                    // The same peer corrected it's previous mistake. There was an error, so we
+                                    // negative score the original peer.
+                                    let action = PeerAction::MidToleranceError;
+                                    debug!(self.log, "Re-processed batch validated by the same peer";
+                                        "batch_epoch" => id, "score_adjustment" => %action,
+                                        "original_peer" => %original_peer, "new_peer" => %processed_attempt.peer_id
+                                    );
+                                    network.report_peer(original_peer, action);
+                                }
                             }
                         }
                     }
@@ -10,8 +10,9 @@ pub use persistence::PersistedOperationPool;
 use attestation::AttMaxCover;
 use attestation_id::AttestationId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::{maximum_cover, MaxCover};
+use max_cover::{maximum_cover, maximum_cover_with_local_search, MaxCover};
 use parking_lot::RwLock;
+use serde_derive::{Deserialize, Serialize};
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, verify_attestation_for_block_inclusion, verify_exit,
@@ -21,6 +22,7 @@ use state_processing::SigVerifiedOp;
 use std::collections::{hash_map, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::str::FromStr;
 use types::{
     typenum::Unsigned, Attestation, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
     Epoch, EthSpec, Fork, ForkVersion, Hash256, ProposerSlashing, RelativeEpoch,
@@ -44,6 +46,55 @@ pub enum OpPoolError {
     GetAttestationsTotalBalanceError(BeaconStateError),
 }
 
+/// The maximum number of swaps made by `AttestationPackingStrategy::LocalSearch` for each epoch.
+const LOCAL_SEARCH_MAX_ROUNDS: usize = 16;
+
+/// The algorithm used to select attestations for inclusion in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AttestationPackingStrategy {
+    /// A greedy approximation of the maximum cover.
+    Greedy,
+    /// The greedy approximation, improved by a bounded local search which swaps attestations in
+    /// and out of the solution whilst doing so increases the proposer reward.
+    LocalSearch,
+}
+
+impl Default for AttestationPackingStrategy {
+    fn default() -> Self {
+        AttestationPackingStrategy::Greedy
+    }
+}
+
+impl FromStr for AttestationPackingStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "greedy" => Ok(AttestationPackingStrategy::Greedy),
+            "local-search" => Ok(AttestationPackingStrategy::LocalSearch),
+            other => Err(format!(
+                "Invalid attestation packing strategy: {}, expected greedy or local-search",
+                other
+            )),
+        }
+    }
+}
+
+/// Select up to `limit` of `attestations` using `strategy`.
+fn pack_attestations<'a, T: EthSpec>(
+    attestations: impl IntoIterator<Item = AttMaxCover<'a, T>>,
+    limit: usize,
+    strategy: AttestationPackingStrategy,
+) -> Vec<AttMaxCover<'a, T>> {
+    match strategy {
+        AttestationPackingStrategy::Greedy => maximum_cover(attestations, limit),
+        AttestationPackingStrategy::LocalSearch => {
+            maximum_cover_with_local_search(attestations, limit, LOCAL_SEARCH_MAX_ROUNDS)
+        }
+    }
+}
+
 impl<T: EthSpec> OperationPool<T> {
     /// Create a new operation pool.
     pub fn new() -> Self {
@@ -144,6 +195,24 @@ impl<T: EthSpec> OperationPool<T> {
         prev_epoch_validity_filter: impl FnMut(&&Attestation<T>) -> bool + Send,
         curr_epoch_validity_filter: impl FnMut(&&Attestation<T>) -> bool + Send,
         spec: &ChainSpec,
+    ) -> Result<Vec<Attestation<T>>, OpPoolError> {
+        self.get_attestations_with_strategy(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            AttestationPackingStrategy::default(),
+            spec,
+        )
+    }
+
+    /// As per `Self::get_attestations`, but using the given `strategy` to select attestations.
+    pub fn get_attestations_with_strategy(
+        &self,
+        state: &BeaconState<T>,
+        prev_epoch_validity_filter: impl FnMut(&&Attestation<T>) -> bool + Send,
+        curr_epoch_validity_filter: impl FnMut(&&Attestation<T>) -> bool + Send,
+        strategy: AttestationPackingStrategy,
+        spec: &ChainSpec,
     ) -> Result<Vec<Attestation<T>>, OpPoolError> {
         // Attestations for the current fork, which may be from the current or previous epoch.
         let prev_epoch = state.previous_epoch();
@@ -188,12 +257,12 @@ impl<T: EthSpec> OperationPool<T> {
                 if prev_epoch == current_epoch {
                     vec![]
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit)
+                    pack_attestations(prev_epoch_att, prev_epoch_limit, strategy)
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(curr_epoch_att, T::MaxAttestations::to_usize())
+                pack_attestations(curr_epoch_att, T::MaxAttestations::to_usize(), strategy)
             },
         );
 
@@ -1,4 +1,6 @@
 use itertools::Itertools;
+use std::collections::HashMap;
+use std::hash::Hash;
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -88,6 +90,171 @@ where
     result
 }
 
+/// An item tagged with its position in the input to `maximum_cover_with_local_search`.
+#[derive(Clone)]
+struct Indexed<T> {
+    index: usize,
+    item: T,
+}
+
+impl<T: MaxCover> MaxCover for Indexed<T> {
+    type Object = T::Object;
+    type Set = T::Set;
+
+    fn object(&self) -> &Self::Object {
+        self.item.object()
+    }
+
+    fn covering_set(&self) -> &Self::Set {
+        self.item.covering_set()
+    }
+
+    fn update_covering_set(&mut self, max_obj: &Self::Object, max_set: &Self::Set) {
+        self.item.update_covering_set(max_obj, max_set)
+    }
+
+    fn score(&self) -> usize {
+        self.item.score()
+    }
+}
+
+/// Compute an approximate maximum cover by improving upon the greedy solution of
+/// `maximum_cover` with a bounded local search.
+///
+/// Each round of the local search finds the single swap of an item in the solution for an item
+/// outside of it which most increases the total weight covered, stopping when no swap improves the
+/// solution or after `max_rounds` rounds. The result is never worse than that of `maximum_cover`.
+///
+/// Sets map each element to its weight, which must be the same for every set containing it.
+///
+/// * Time complexity: `O(limit * items_iter.len() + max_rounds * total_set_size)`
+/// * Space complexity: `O(total_set_size)`
+pub fn maximum_cover_with_local_search<I, T, K>(
+    items_iter: I,
+    limit: usize,
+    max_rounds: usize,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover<Set = HashMap<K, u64>>,
+    K: Clone + Eq + Hash,
+{
+    let items: Vec<T> = items_iter
+        .into_iter()
+        .filter(|item| item.score() != 0)
+        .collect();
+
+    let indexed_items = items
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(index, item)| Indexed { index, item });
+    let mut in_solution = vec![false; items.len()];
+    let mut solution: Vec<usize> = maximum_cover(indexed_items, limit)
+        .into_iter()
+        .map(|indexed| indexed.index)
+        .collect();
+    solution.iter().for_each(|i| in_solution[*i] = true);
+
+    // Map each element to the number of items in the solution which cover it.
+    let mut coverage: HashMap<K, usize> = HashMap::new();
+    for &i in &solution {
+        for element in items[i].covering_set().keys() {
+            *coverage.entry(element.clone()).or_default() += 1;
+        }
+    }
+
+    for _ in 0..max_rounds {
+        // The weight which would be newly covered by adding each item outside the solution.
+        let added: Vec<u64> = items
+            .iter()
+            .enumerate()
+            .map(|(j, item)| {
+                if in_solution[j] {
+                    return 0;
+                }
+                item.covering_set()
+                    .iter()
+                    .filter(|(element, _)| !coverage.contains_key(*element))
+                    .map(|(_, weight)| *weight)
+                    .sum()
+            })
+            .collect();
+
+        // Map each element covered by only one item in the solution to that item's position in
+        // `solution`. Removing that item would uncover these elements.
+        let mut unique_owner: HashMap<&K, usize> = HashMap::new();
+        let mut unique_weight = vec![0; solution.len()];
+        for (position, &i) in solution.iter().enumerate() {
+            for (element, weight) in items[i].covering_set() {
+                if coverage.get(element) == Some(&1) {
+                    unique_owner.insert(element, position);
+                    unique_weight[position] += weight;
+                }
+            }
+        }
+
+        // For each outside item, the weight it shares with the unique elements of each solution
+        // item. Only pairs which overlap are recorded.
+        let mut overlaps: HashMap<(usize, usize), u64> = HashMap::new();
+        for (j, item) in items.iter().enumerate().filter(|(j, _)| !in_solution[*j]) {
+            for (element, weight) in item.covering_set() {
+                if let Some(&position) = unique_owner.get(element) {
+                    *overlaps.entry((position, j)).or_default() += weight;
+                }
+            }
+        }
+
+        // Swapping solution item `position` for outside item `j` changes the covered weight by
+        // `added[j] + overlap - unique_weight[position]`. The best non-overlapping outside item
+        // is always a candidate, so only it and the overlapping pairs need to be considered.
+        let added = &added;
+        let best_outside = (0..items.len())
+            .filter(|j| !in_solution[*j])
+            .max_by_key(|j| added[*j]);
+        let best_swap = overlaps
+            .iter()
+            .map(|(&(position, j), overlap)| (position, j, added[j] + overlap))
+            .chain(
+                best_outside
+                    .into_iter()
+                    .flat_map(|j| (0..solution.len()).map(move |position| (position, j, added[j]))),
+            )
+            .filter(|(position, _, gain)| *gain > unique_weight[*position])
+            .max_by_key(|(position, _, gain)| gain - unique_weight[*position]);
+
+        let (position, j) = match best_swap {
+            Some((position, j, _)) => (position, j),
+            None => break,
+        };
+
+        let removed = solution[position];
+        for element in items[removed].covering_set().keys() {
+            if let Some(count) = coverage.get_mut(element) {
+                *count -= 1;
+                if *count == 0 {
+                    coverage.remove(element);
+                }
+            }
+        }
+        for element in items[j].covering_set().keys() {
+            *coverage.entry(element.clone()).or_default() += 1;
+        }
+        in_solution[removed] = false;
+        in_solution[j] = true;
+        solution[position] = j;
+    }
+
+    // Re-run the greedy algorithm over the chosen items so that the result is ordered and scored
+    // in the same way as the output of `maximum_cover`.
+    let chosen = items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| in_solution[*i])
+        .map(|(_, item)| item);
+    maximum_cover(chosen, limit)
+}
+
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
 pub fn merge_solutions<I1, I2, T>(cover1: I1, cover2: I2, limit: usize) -> Vec<T::Object>
 where
@@ -121,7 +288,7 @@ mod test {
         }
 
         fn covering_set(&self) -> &Self {
-            &self
+            self
         }
 
         fn update_covering_set(&mut self, _: &Self, other: &Self) {
@@ -134,6 +301,37 @@ mod test {
         }
     }
 
+    impl<K> MaxCover for HashMap<K, u64>
+    where
+        K: Clone + Eq + Hash,
+    {
+        type Object = Self;
+        type Set = Self;
+
+        fn object(&self) -> &Self {
+            self
+        }
+
+        fn covering_set(&self) -> &Self {
+            self
+        }
+
+        fn update_covering_set(&mut self, _: &Self, other: &Self) {
+            self.retain(|k, _| !other.contains_key(k));
+        }
+
+        fn score(&self) -> usize {
+            self.values().sum::<u64>() as usize
+        }
+    }
+
+    /// Convert sets to maps with a weight of 1 for every element.
+    fn unit_weights(sets: Vec<HashSet<usize>>) -> Vec<HashMap<usize, u64>> {
+        sets.into_iter()
+            .map(|set| set.into_iter().map(|k| (k, 1)).collect())
+            .collect()
+    }
+
     fn example_system() -> Vec<HashSet<usize>> {
         vec![
             HashSet::from_iter(vec![3]),
@@ -206,4 +404,57 @@ mod test {
         assert_eq!(quality(&cover), 19);
         assert_eq!(cover.len(), 5);
     }
+
+    fn weighted_quality(solution: &[HashMap<usize, u64>]) -> u64 {
+        let mut covered = HashMap::new();
+        for set in solution {
+            covered.extend(set.iter());
+        }
+        covered.values().sum()
+    }
+
+    // Greedy selects the first set (quality 4) followed by one of the others (quality 5), whilst
+    // the optimal solution is the last two sets (quality 6).
+    #[test]
+    fn local_search_improves_greedy() {
+        let sets = unit_weights(vec![
+            HashSet::from_iter(vec![1, 2, 3, 4]),
+            HashSet::from_iter(vec![1, 2, 5]),
+            HashSet::from_iter(vec![3, 4, 6]),
+        ]);
+
+        let greedy = maximum_cover(sets.clone(), 2);
+        assert_eq!(weighted_quality(&greedy), 5);
+
+        let cover = maximum_cover_with_local_search(sets.clone(), 2, 1);
+        assert_eq!(cover.len(), 2);
+        assert_eq!(weighted_quality(&cover), 6);
+
+        let no_rounds = maximum_cover_with_local_search(sets, 2, 0);
+        assert_eq!(weighted_quality(&no_rounds), 5);
+    }
+
+    #[test]
+    fn local_search_never_worse_than_greedy() {
+        let systems = vec![
+            example_system(),
+            vec![
+                HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+                HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+                HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+                HashSet::from_iter(vec![9, 10]),
+                HashSet::from_iter(vec![5, 6, 7, 8]),
+                HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+            ],
+        ];
+
+        for sets in systems.into_iter().map(unit_weights) {
+            for limit in 0..sets.len() + 1 {
+                let greedy = maximum_cover(sets.clone(), limit);
+                let cover = maximum_cover_with_local_search(sets.clone(), limit, 16);
+                assert!(cover.len() <= limit);
+                assert!(weighted_quality(&cover) >= weighted_quality(&greedy));
+            }
+        }
+    }
 }
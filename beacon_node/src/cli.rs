@@ -83,6 +83,39 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("50")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("dynamic-target-peers")
+                .long("dynamic-target-peers")
+                .help("Automatically raise the target number of peers above --target-peers whilst \
+                       syncing or when serving many validators. The target will never exceed \
+                       twice the value of --target-peers.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("parent-lookup-max-download-attempts")
+                .long("parent-lookup-max-download-attempts")
+                .value_name("COUNT")
+                .help("The number of times the parent of a block may fail to download from a peer \
+                       before the search for its ancestors is abandoned.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("parent-lookup-max-processing-attempts")
+                .long("parent-lookup-max-processing-attempts")
+                .value_name("COUNT")
+                .help("The number of times a peer may return the wrong parent of a block before \
+                       the search for its ancestors is abandoned.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("parent-lookup-retry-delay")
+                .long("parent-lookup-retry-delay")
+                .value_name("MILLISECONDS")
+                .help("The delay before retrying a failed parent lookup request. The delay is \
+                       multiplied by the number of failed attempts for the lookup. A value of \
+                       0 retries immediately.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("boot-nodes")
                 .long("boot-nodes")
@@ -467,6 +500,54 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("WSS_CHECKPOINT")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("persist-metrics")
+                .long("persist-metrics")
+                .help("Periodically save selected metrics counters (e.g., re-orgs, block \
+                    production and missed attestations of monitored validators) to the database \
+                    and restore them at startup, so they are not reset by a restart.")
+        )
+        .arg(
+            Arg::with_name("disk-space-threshold")
+                .long("disk-space-threshold")
+                .help("Enter a protective mode when the free space on the disk containing the \
+                    database drops below this value. Whilst in protective mode non-critical \
+                    database writes (e.g., persisting the operation pool) are disabled to reduce \
+                    the risk of database corruption. Disk space is not monitored if this flag is \
+                    not supplied.")
+                .value_name("MEGABYTES")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("attestation-packing-strategy")
+                .long("attestation-packing-strategy")
+                .help("The algorithm used to select attestations for inclusion in produced \
+                    blocks. \"local-search\" improves upon the default greedy selection by \
+                    swapping attestations whilst doing so increases the proposer reward, at the \
+                    cost of additional block production time.")
+                .value_name("STRATEGY")
+                .possible_values(&["greedy", "local-search"])
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("maximum-gossip-clock-disparity")
+                .long("maximum-gossip-clock-disparity")
+                .help("Overrides the network's MAXIMUM_GOSSIP_CLOCK_DISPARITY, the tolerance \
+                    for clock differences between nodes when validating the slot of gossip \
+                    blocks and attestations. Only change this from the default if you know \
+                    what you are doing.")
+                .value_name("MILLISECONDS")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("attestation-propagation-slot-range")
+                .long("attestation-propagation-slot-range")
+                .help("Overrides the network's ATTESTATION_PROPAGATION_SLOT_RANGE, the number of \
+                    slots for which attestations are accepted on gossip. Values larger than \
+                    SLOTS_PER_EPOCH have no effect.")
+                .value_name("SLOTS")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("validator-monitor-auto")
                 .long("validator-monitor-auto")
@@ -13,6 +13,7 @@ use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
 use std::net::{TcpListener, UdpSocket};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use types::{ChainSpec, Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes, GRAFFITI_BYTES_LEN};
 
 /// Gets the fully-initialized global client.
@@ -363,6 +364,25 @@ pub fn get_config<E: EthSpec>(
         };
     }
 
+    if cli_args.is_present("persist-metrics") {
+        client_config.chain.persist_metrics = true;
+    }
+
+    if let Some(threshold_mbs) =
+        clap_utils::parse_optional::<u64>(cli_args, "disk-space-threshold")?
+    {
+        client_config.chain.disk_space_threshold = Some(threshold_mbs.saturating_mul(1024 * 1024));
+    }
+
+    if let Some(strategy) = clap_utils::parse_optional(cli_args, "attestation-packing-strategy")? {
+        client_config.chain.attestation_packing_strategy = strategy;
+    }
+
+    client_config.chain.maximum_gossip_clock_disparity_millis =
+        clap_utils::parse_optional(cli_args, "maximum-gossip-clock-disparity")?;
+    client_config.chain.attestation_propagation_slot_range =
+        clap_utils::parse_optional(cli_args, "attestation-propagation-slot-range")?;
+
     if cli_args.is_present("slasher") {
         let slasher_dir = if let Some(slasher_dir) = cli_args.value_of("slasher-dir") {
             PathBuf::from(slasher_dir)
@@ -476,6 +496,26 @@ pub fn set_network_config(
             .map_err(|_| format!("Invalid number of target peers: {}", target_peers_str))?;
     }
 
+    if cli_args.is_present("dynamic-target-peers") {
+        config.dynamic_target_peers = true;
+    }
+
+    if let Some(attempts) =
+        clap_utils::parse_optional(cli_args, "parent-lookup-max-download-attempts")?
+    {
+        config.parent_lookup.max_download_attempts = attempts;
+    }
+
+    if let Some(attempts) =
+        clap_utils::parse_optional(cli_args, "parent-lookup-max-processing-attempts")?
+    {
+        config.parent_lookup.max_processing_attempts = attempts;
+    }
+
+    if let Some(delay_ms) = clap_utils::parse_optional(cli_args, "parent-lookup-retry-delay")? {
+        config.parent_lookup.retry_delay = Duration::from_millis(delay_ms);
+    }
+
     if let Some(port_str) = cli_args.value_of("port") {
         let port = port_str
             .parse::<u16>()
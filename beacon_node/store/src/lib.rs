@@ -165,6 +165,14 @@ pub enum DBColumn {
     BeaconHistoricalRoots,
     BeaconRandaoMixes,
     DhtEnrs,
+    /// For the scores of peers, retained across restarts.
+    PeerScores,
+    /// For the pubkeys registered with the validator monitor at runtime.
+    ValidatorMonitor,
+    /// For metrics counters which are retained across restarts.
+    PersistedMetrics,
+    /// For the recent epoch summaries of monitored validators.
+    ValidatorMonitorSummaries,
 }
 
 impl Into<&'static str> for DBColumn {
@@ -187,6 +195,10 @@ impl Into<&'static str> for DBColumn {
             DBColumn::BeaconHistoricalRoots => "bhr",
             DBColumn::BeaconRandaoMixes => "brm",
             DBColumn::DhtEnrs => "dht",
+            DBColumn::PeerScores => "psc",
+            DBColumn::ValidatorMonitor => "vmo",
+            DBColumn::PersistedMetrics => "pmt",
+            DBColumn::ValidatorMonitorSummaries => "vms",
         }
     }
 }
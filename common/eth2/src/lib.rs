@@ -109,6 +109,11 @@ impl BeaconNodeHttpClient {
         Self { client, server }
     }
 
+    /// Returns a client for the same server which sends its requests using `client`.
+    pub fn with_client(&self, client: reqwest::Client) -> Self {
+        Self::from_components(self.server.clone(), client)
+    }
+
     /// Return the path with the standard `/eth1/v1` prefix applied.
     fn eth_path(&self) -> Result<Url, Error> {
         let mut path = self.server.full.clone();
@@ -190,6 +195,19 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
+    /// Perform a HTTP DELETE request.
+    async fn delete<T: Serialize, U: IntoUrl>(&self, url: U, body: &T) -> Result<(), Error> {
+        let response = self
+            .client
+            .delete(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(Error::Reqwest)?;
+        ok_or_error(response).await?;
+        Ok(())
+    }
+
     /// Perform a HTTP POST request, returning a JSON response.
     async fn post_with_response<T: DeserializeOwned, U: IntoUrl, V: Serialize>(
         &self,
@@ -582,6 +600,41 @@ impl BeaconNodeHttpClient {
         slot: Option<Slot>,
         committee_index: Option<u64>,
     ) -> Result<GenericResponse<Vec<Attestation<T>>>, Error> {
+        let query = AttestationPoolQuery {
+            slot,
+            committee_index,
+            ..AttestationPoolQuery::default()
+        };
+        self.get_beacon_pool_attestations_with_query(&query).await
+    }
+
+    /// `GET beacon/pool/attestations?slot,committee_index,from_slot,to_slot,only_includable`
+    pub async fn get_beacon_pool_attestations_with_query<T: EthSpec>(
+        &self,
+        query: &AttestationPoolQuery,
+    ) -> Result<GenericResponse<Vec<Attestation<T>>>, Error> {
+        let path = self.beacon_pool_attestations_path(query)?;
+        self.get(path).await
+    }
+
+    /// `GET beacon/pool/attestations?slot,committee_index,from_slot,to_slot,only_includable`
+    /// `-H "accept: application/octet-stream"`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_pool_attestations_ssz<T: EthSpec>(
+        &self,
+        query: &AttestationPoolQuery,
+    ) -> Result<Option<Vec<Attestation<T>>>, Error> {
+        let path = self.beacon_pool_attestations_path(query)?;
+
+        self.get_bytes_opt_accept_header(path, Accept::Ssz)
+            .await?
+            .map(|bytes| Vec::<Attestation<T>>::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
+    /// Returns the path for `GET beacon/pool/attestations` with all query parameters applied.
+    fn beacon_pool_attestations_path(&self, query: &AttestationPoolQuery) -> Result<Url, Error> {
         let mut path = self.eth_path()?;
 
         path.path_segments_mut()
@@ -590,17 +643,32 @@ impl BeaconNodeHttpClient {
             .push("pool")
             .push("attestations");
 
-        if let Some(slot) = slot {
+        if let Some(slot) = query.slot {
             path.query_pairs_mut()
                 .append_pair("slot", &slot.to_string());
         }
 
-        if let Some(index) = committee_index {
+        if let Some(index) = query.committee_index {
             path.query_pairs_mut()
                 .append_pair("committee_index", &index.to_string());
         }
 
-        self.get(path).await
+        if let Some(from_slot) = query.from_slot {
+            path.query_pairs_mut()
+                .append_pair("from_slot", &from_slot.to_string());
+        }
+
+        if let Some(to_slot) = query.to_slot {
+            path.query_pairs_mut()
+                .append_pair("to_slot", &to_slot.to_string());
+        }
+
+        if let Some(only_includable) = query.only_includable {
+            path.query_pairs_mut()
+                .append_pair("only_includable", &only_includable.to_string());
+        }
+
+        Ok(path)
     }
 
     /// `POST beacon/pool/attester_slashings`
@@ -2,7 +2,10 @@
 
 use crate::{
     ok_or_error,
-    types::{BeaconState, Epoch, EthSpec, GenericResponse, ValidatorId},
+    types::{
+        Accept, BeaconState, BeaconStateSummary, Epoch, EthSpec, GenericResponse, PublicKeyBytes,
+        SignedBeaconBlock, Slot, ValidatorId,
+    },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, StateId, StatusCode,
 };
 use proto_array::core::ProtoArray;
@@ -11,7 +14,10 @@ use serde::{Deserialize, Serialize};
 use ssz::Decode;
 use ssz_derive::{Decode, Encode};
 
-pub use eth2_libp2p::{types::SyncState, PeerInfo};
+pub use eth2_libp2p::{
+    types::{BlockLookupInfo, BlockLookupKind, BlockLookupState, SyncState},
+    PeerInfo,
+};
 
 /// Information returned by `peers` and `connected_peers`.
 // TODO: this should be deserializable..
@@ -75,6 +81,136 @@ pub struct ValidatorInclusionData {
     pub is_previous_epoch_head_attester: bool,
 }
 
+/// Indicates that messages for a validator were received from more than one validator client
+/// instance, which is likely to result in a slashable offence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateValidatorClient {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The epoch in which the duplicate messages were observed.
+    pub epoch: Epoch,
+    /// The instance identifiers of the validator clients which sent the messages.
+    pub instances: Vec<String>,
+}
+
+/// The free disk space as observed by the beacon node's disk space monitor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskSpaceStatus {
+    /// The free space on the disk(s) containing the database, or `None` if the disk space monitor
+    /// is disabled or has not yet run.
+    pub free_bytes: Option<u64>,
+    /// The free space below which the node enters protective mode.
+    pub threshold_bytes: Option<u64>,
+    /// `true` if non-critical database writes are disabled due to low disk space.
+    pub protective_mode: bool,
+}
+
+/// Decodes the blocks returned by `GET lighthouse/database/blocks`, each of which is prefixed with
+/// its length as a 4-byte little-endian integer.
+fn decode_block_frames<E: EthSpec>(
+    mut bytes: &[u8],
+) -> Result<Vec<SignedBeaconBlock<E>>, ssz::DecodeError> {
+    const PREFIX_LEN: usize = 4;
+
+    let mut blocks = vec![];
+    while !bytes.is_empty() {
+        if bytes.len() < PREFIX_LEN {
+            return Err(ssz::DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: PREFIX_LEN,
+            });
+        }
+        let mut prefix = [0; PREFIX_LEN];
+        prefix.copy_from_slice(&bytes[..PREFIX_LEN]);
+        let len = u32::from_le_bytes(prefix) as usize;
+        bytes = &bytes[PREFIX_LEN..];
+
+        if bytes.len() < len {
+            return Err(ssz::DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: len,
+            });
+        }
+        blocks.push(SignedBeaconBlock::from_ssz_bytes(&bytes[..len])?);
+        bytes = &bytes[len..];
+    }
+
+    Ok(blocks)
+}
+
+/// Query parameters for `GET lighthouse/analysis/reorg_impact`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReorgImpactQuery {
+    pub root: Hash256,
+}
+
+/// The expected impact of fork choice switching from the current head to a candidate head.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReorgImpact {
+    pub head_root: Hash256,
+    /// The fork choice weight of the current head, in Gwei.
+    pub head_weight: u64,
+    pub candidate_root: Hash256,
+    /// The fork choice weight of the candidate head, in Gwei.
+    pub candidate_weight: u64,
+    /// The most recent block which is an ancestor of both the head and the candidate.
+    pub common_ancestor_root: Hash256,
+    pub common_ancestor_slot: Slot,
+    /// Blocks on the canonical chain which would be orphaned, from newest to oldest.
+    pub orphaned_blocks: Vec<Hash256>,
+    /// Attestations included in orphaned blocks which are not included on the candidate chain.
+    pub attestations_to_reinclude: u64,
+    /// Slashings and voluntary exits included in orphaned blocks which are not included on the
+    /// candidate chain.
+    pub operations_to_reinclude: u64,
+    /// Attestations in the operation pool which vote for an orphaned block.
+    pub pool_attestations_for_orphaned_blocks: u64,
+    /// Attestations, slashings and voluntary exits in the operation pool which are already
+    /// included on the candidate chain, and would therefore become invalid.
+    pub invalidated_pool_operations: u64,
+}
+
+/// Query parameters for `GET lighthouse/ui/health_timeline`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthTimelineQuery {
+    pub slots: Option<u64>,
+}
+
+/// A summary of the chain's health at a single slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotHealth {
+    pub slot: Slot,
+    /// The canonical block at this slot, or `None` if the slot was skipped.
+    pub block_root: Option<Hash256>,
+    /// The delay between the start of the slot and when the canonical block was imported, if it
+    /// was imported recently by this node.
+    pub block_import_delay_ms: Option<u64>,
+    /// The proportion of the slot's committees whose attestations have been included in the
+    /// canonical chain, if the slot is in the previous or current epoch of the head state.
+    pub attestation_participation: Option<f64>,
+    /// True if a block at this slot was imported but is no longer on the canonical chain.
+    pub reorged: bool,
+}
+
+/// Query parameters for `GET lighthouse/database/blocks`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseBlocksQuery {
+    pub start_slot: Slot,
+    pub end_slot: Slot,
+}
+
+/// The slot and state root of a block, as returned by `POST lighthouse/beacon/block_roots`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedBlockRoot {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    pub state_root: Hash256,
+    /// True if the block is on the canonical chain, false if it has been orphaned.
+    pub canonical: bool,
+    /// True if the block is canonical and finalized.
+    pub finalized: bool,
+}
+
 #[cfg(target_os = "linux")]
 use {
     procinfo::pid, psutil::cpu::os::linux::CpuTimesExt,
@@ -354,6 +490,74 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/duplicate_validator_clients`
+    pub async fn get_lighthouse_duplicate_validator_clients(
+        &self,
+    ) -> Result<GenericResponse<Vec<DuplicateValidatorClient>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duplicate_validator_clients");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/disk_space`
+    pub async fn get_lighthouse_disk_space(
+        &self,
+    ) -> Result<GenericResponse<DiskSpaceStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("disk_space");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/analysis/reorg_impact?root`
+    pub async fn get_lighthouse_analysis_reorg_impact(
+        &self,
+        root: Hash256,
+    ) -> Result<GenericResponse<ReorgImpact>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("reorg_impact");
+
+        path.query_pairs_mut()
+            .append_pair("root", &format!("{:?}", root));
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/ui/health_timeline`
+    pub async fn get_lighthouse_ui_health_timeline(
+        &self,
+        slots: Option<u64>,
+    ) -> Result<GenericResponse<Vec<SlotHealth>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("health_timeline");
+
+        if let Some(slots) = slots {
+            path.query_pairs_mut()
+                .append_pair("slots", &slots.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/syncing`
     pub async fn get_lighthouse_syncing(&self) -> Result<GenericResponse<SyncState>, Error> {
         let mut path = self.server.full.clone();
@@ -366,6 +570,21 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/sync/lookups`
+    pub async fn get_lighthouse_sync_lookups(
+        &self,
+    ) -> Result<GenericResponse<Vec<BlockLookupInfo>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("lookups");
+
+        self.get(path).await
+    }
+
     /*
      * Note:
      *
@@ -487,6 +706,88 @@ impl BeaconNodeHttpClient {
             .transpose()
     }
 
+    /// `GET lighthouse/beacon/states/{state_id}/summary`
+    pub async fn get_lighthouse_beacon_states_summary(
+        &self,
+        state_id: &StateId,
+    ) -> Result<Option<GenericResponse<BeaconStateSummary>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("summary");
+
+        self.get_opt(path).await
+    }
+
+    /// `GET lighthouse/beacon/states/{state_id}/summary`
+    /// `-H "accept: application/octet-stream"`
+    pub async fn get_lighthouse_beacon_states_summary_ssz(
+        &self,
+        state_id: &StateId,
+    ) -> Result<Option<BeaconStateSummary>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("summary");
+
+        self.get_bytes_opt_accept_header(path, Accept::Ssz)
+            .await?
+            .map(|bytes| BeaconStateSummary::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
+    /// `POST lighthouse/beacon/block_roots`
+    pub async fn post_lighthouse_beacon_block_roots(
+        &self,
+        block_roots: &[Hash256],
+    ) -> Result<GenericResponse<Vec<ResolvedBlockRoot>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("block_roots");
+
+        self.post_with_response(path, &block_roots).await
+    }
+
+    /// `GET lighthouse/database/blocks?start_slot,end_slot`
+    ///
+    /// Returns the canonical blocks from `start_slot` to `end_slot` (inclusive).
+    pub async fn get_lighthouse_database_blocks<E: EthSpec>(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<Option<Vec<SignedBeaconBlock<E>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("blocks");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &start_slot.to_string())
+            .append_pair("end_slot", &end_slot.to_string());
+
+        self.get_bytes_opt(path)
+            .await?
+            .map(|bytes| decode_block_frames(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
     /// `GET lighthouse/staking`
     pub async fn get_lighthouse_staking(&self) -> Result<bool, Error> {
         let mut path = self.server.full.clone();
@@ -498,4 +799,36 @@ impl BeaconNodeHttpClient {
 
         self.get_opt::<(), _>(path).await.map(|opt| opt.is_some())
     }
+
+    /// `POST lighthouse/validator_monitor/validators`
+    pub async fn post_lighthouse_validator_monitor_validators(
+        &self,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push("validators");
+
+        self.post(path, &pubkeys).await
+    }
+
+    /// `DELETE lighthouse/validator_monitor/validators`
+    pub async fn delete_lighthouse_validator_monitor_validators(
+        &self,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push("validators");
+
+        self.delete(path, &pubkeys).await
+    }
 }
@@ -10,6 +10,13 @@ use std::fmt;
 use std::str::{from_utf8, FromStr};
 pub use types::*;
 
+/// The HTTP header used by the validator client to identify itself to the beacon node.
+///
+/// Each validator client process generates a random value at startup and includes it in all
+/// requests. The beacon node uses it to detect the same validator being run by more than one
+/// validator client.
+pub const VALIDATOR_CLIENT_INSTANCE_HEADER: &str = "X-Lighthouse-Validator-Client-Instance";
+
 /// An API error serializable to JSON.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ErrorMessage {
@@ -52,6 +59,65 @@ pub struct GenesisData {
     pub genesis_fork_version: [u8; 4],
 }
 
+/// A named point in the chain from which a relative block or state identifier is offset.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RelativeAnchor {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+}
+
+impl FromStr for RelativeAnchor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(RelativeAnchor::Head),
+            "genesis" => Ok(RelativeAnchor::Genesis),
+            "finalized" => Ok(RelativeAnchor::Finalized),
+            "justified" => Ok(RelativeAnchor::Justified),
+            other => Err(format!("{} is not a valid anchor", other)),
+        }
+    }
+}
+
+impl fmt::Display for RelativeAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelativeAnchor::Head => write!(f, "head"),
+            RelativeAnchor::Genesis => write!(f, "genesis"),
+            RelativeAnchor::Finalized => write!(f, "finalized"),
+            RelativeAnchor::Justified => write!(f, "justified"),
+        }
+    }
+}
+
+/// Attempts to parse `s` as a relative identifier, e.g. `head-32` or `finalized+1`.
+///
+/// Returns `None` if `s` does not take the form `<anchor>+<slots>` or `<anchor>-<slots>`.
+fn parse_relative(s: &str) -> Option<Result<(RelativeAnchor, i64), String>> {
+    let i = s.find(|c| c == '+' || c == '-')?;
+    let anchor = RelativeAnchor::from_str(&s[..i]).ok()?;
+
+    let offset = i64::from_str(&s[i + 1..])
+        .ok()
+        .filter(|offset| *offset >= 0)
+        .map(|offset| if &s[i..=i] == "-" { -offset } else { offset })
+        .ok_or_else(|| format!("{} cannot be parsed as a slot offset", &s[i + 1..]));
+
+    Some(offset.map(|offset| (anchor, offset)))
+}
+
+/// Formats a relative identifier such that it can be parsed by `parse_relative`.
+fn fmt_relative(f: &mut fmt::Formatter<'_>, anchor: &RelativeAnchor, offset: i64) -> fmt::Result {
+    if offset < 0 {
+        write!(f, "{}-{}", anchor, offset.abs())
+    } else {
+        write!(f, "{}+{}", anchor, offset)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BlockId {
     Head,
@@ -60,6 +126,11 @@ pub enum BlockId {
     Justified,
     Slot(Slot),
     Root(Hash256),
+    /// The block at `offset` slots from the slot of `anchor`.
+    Relative {
+        anchor: RelativeAnchor,
+        offset: i64,
+    },
 }
 
 impl FromStr for BlockId {
@@ -76,6 +147,8 @@ impl FromStr for BlockId {
                     Hash256::from_str(&s[2..])
                         .map(BlockId::Root)
                         .map_err(|e| format!("{} cannot be parsed as a root", e))
+                } else if let Some(relative) = parse_relative(other) {
+                    relative.map(|(anchor, offset)| BlockId::Relative { anchor, offset })
                 } else {
                     u64::from_str(s)
                         .map(Slot::new)
@@ -96,6 +169,7 @@ impl fmt::Display for BlockId {
             BlockId::Justified => write!(f, "justified"),
             BlockId::Slot(slot) => write!(f, "{}", slot),
             BlockId::Root(root) => write!(f, "{:?}", root),
+            BlockId::Relative { anchor, offset } => fmt_relative(f, anchor, *offset),
         }
     }
 }
@@ -108,6 +182,11 @@ pub enum StateId {
     Justified,
     Slot(Slot),
     Root(Hash256),
+    /// The state at `offset` slots from the slot of `anchor`.
+    Relative {
+        anchor: RelativeAnchor,
+        offset: i64,
+    },
 }
 
 impl FromStr for StateId {
@@ -124,6 +203,8 @@ impl FromStr for StateId {
                     Hash256::from_str(&s[2..])
                         .map(StateId::Root)
                         .map_err(|e| format!("{} cannot be parsed as a root", e))
+                } else if let Some(relative) = parse_relative(other) {
+                    relative.map(|(anchor, offset)| StateId::Relative { anchor, offset })
                 } else {
                     u64::from_str(s)
                         .map(Slot::new)
@@ -144,6 +225,7 @@ impl fmt::Display for StateId {
             StateId::Justified => write!(f, "justified"),
             StateId::Slot(slot) => write!(f, "{}", slot),
             StateId::Root(root) => write!(f, "{:?}", root),
+            StateId::Relative { anchor, offset } => fmt_relative(f, anchor, *offset),
         }
     }
 }
@@ -378,10 +460,32 @@ pub struct CommitteesQuery {
     pub epoch: Option<Epoch>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
     pub committee_index: Option<u64>,
+    /// Only return attestations with a slot greater than or equal to `from_slot`.
+    pub from_slot: Option<Slot>,
+    /// Only return attestations with a slot less than or equal to `to_slot`.
+    pub to_slot: Option<Slot>,
+    /// Only return attestations which may be included in a block at the current slot, i.e. those
+    /// within the attestation inclusion window.
+    pub only_includable: Option<bool>,
+}
+
+impl AttestationPoolQuery {
+    /// Returns `true` if an attestation with `data` matches all filters except `only_includable`,
+    /// which requires knowledge of the current slot.
+    pub fn matches(&self, data: &AttestationData) -> bool {
+        self.slot.map_or(true, |slot| slot == data.slot)
+            && self
+                .committee_index
+                .map_or(true, |index| index == data.index)
+            && self
+                .from_slot
+                .map_or(true, |from_slot| data.slot >= from_slot)
+            && self.to_slot.map_or(true, |to_slot| data.slot <= to_slot)
+    }
 }
 
 #[derive(Deserialize)]
@@ -689,6 +793,16 @@ pub struct SseHead {
     pub epoch_transition: bool,
 }
 
+/// Emitted when a re-org changes the dependent roots of the current head, indicating that
+/// previously-computed proposer or attester duties may no longer be valid.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseDependentRoot {
+    pub slot: Slot,
+    pub block: Hash256,
+    pub current_duty_dependent_root: Hash256,
+    pub previous_duty_dependent_root: Hash256,
+}
+
 #[derive(PartialEq, Debug, Serialize, Clone)]
 #[serde(bound = "T: EthSpec", untagged)]
 pub enum EventKind<T: EthSpec> {
@@ -697,6 +811,7 @@ pub enum EventKind<T: EthSpec> {
     FinalizedCheckpoint(SseFinalizedCheckpoint),
     Head(SseHead),
     VoluntaryExit(SignedVoluntaryExit),
+    DependentRoot(SseDependentRoot),
 }
 
 impl<T: EthSpec> EventKind<T> {
@@ -707,6 +822,7 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::Attestation(_) => "attestation",
             EventKind::VoluntaryExit(_) => "voluntary_exit",
             EventKind::FinalizedCheckpoint(_) => "finalized_checkpoint",
+            EventKind::DependentRoot(_) => "dependent_root",
         }
     }
 
@@ -748,6 +864,11 @@ impl<T: EthSpec> EventKind<T> {
                     ServerError::InvalidServerSentEvent(format!("Voluntary Exit: {:?}", e))
                 })?,
             )),
+            "dependent_root" => Ok(EventKind::DependentRoot(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Dependent Root: {:?}", e))
+                })?,
+            )),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -768,6 +889,7 @@ pub enum EventTopic {
     Attestation,
     VoluntaryExit,
     FinalizedCheckpoint,
+    DependentRoot,
 }
 
 impl FromStr for EventTopic {
@@ -780,6 +902,7 @@ impl FromStr for EventTopic {
             "attestation" => Ok(EventTopic::Attestation),
             "voluntary_exit" => Ok(EventTopic::VoluntaryExit),
             "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
+            "dependent_root" => Ok(EventTopic::DependentRoot),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -793,6 +916,7 @@ impl fmt::Display for EventTopic {
             EventTopic::Attestation => write!(f, "attestation"),
             EventTopic::VoluntaryExit => write!(f, "voluntary_exit"),
             EventTopic::FinalizedCheckpoint => write!(f, "finalized_checkpoint"),
+            EventTopic::DependentRoot => write!(f, "dependent_root"),
         }
     }
 }
@@ -838,4 +962,49 @@ mod tests {
             QueryVec(vec![0_u64, 1, 2])
         );
     }
+
+    #[test]
+    fn dependent_root_event() {
+        let event = SseDependentRoot {
+            slot: Slot::new(33),
+            block: Hash256::repeat_byte(1),
+            current_duty_dependent_root: Hash256::repeat_byte(2),
+            previous_duty_dependent_root: Hash256::repeat_byte(3),
+        };
+        let bytes = format!(
+            "event:dependent_root\ndata:{}\n\n",
+            serde_json::to_string(&event).unwrap()
+        );
+
+        assert_eq!(
+            EventKind::<MainnetEthSpec>::from_sse_bytes(bytes.as_bytes()).unwrap(),
+            EventKind::DependentRoot(event)
+        );
+        assert_eq!(
+            "dependent_root".parse::<EventTopic>().unwrap(),
+            EventTopic::DependentRoot
+        );
+    }
+
+    #[test]
+    fn relative_ids() {
+        let head_minus_32 = BlockId::Relative {
+            anchor: RelativeAnchor::Head,
+            offset: -32,
+        };
+        assert_eq!(BlockId::from_str("head-32").unwrap(), head_minus_32);
+        assert_eq!(head_minus_32.to_string(), "head-32");
+
+        let finalized_plus_1 = StateId::Relative {
+            anchor: RelativeAnchor::Finalized,
+            offset: 1,
+        };
+        assert_eq!(StateId::from_str("finalized+1").unwrap(), finalized_plus_1);
+        assert_eq!(finalized_plus_1.to_string(), "finalized+1");
+
+        assert!(BlockId::from_str("head-").is_err());
+        assert!(BlockId::from_str("head--1").is_err());
+        assert!(StateId::from_str("finalized+x").is_err());
+        assert!(StateId::from_str("tail+1").is_err());
+    }
 }
@@ -3,12 +3,14 @@ extern crate lazy_static;
 
 mod manual_slot_clock;
 mod metrics;
+mod slot_event_bus;
 mod system_time_slot_clock;
 
 use std::time::Duration;
 
 pub use crate::manual_slot_clock::ManualSlotClock;
 pub use crate::manual_slot_clock::ManualSlotClock as TestingSlotClock;
+pub use crate::slot_event_bus::{SlotEvent, SlotEventBus, SlotOffset};
 pub use crate::system_time_slot_clock::SystemTimeSlotClock;
 pub use metrics::scrape_for_metrics;
 pub use types::Slot;
@@ -75,16 +77,4 @@ pub trait SlotClock: Send + Sync + Sized + Clone {
         self.slot_of(self.now_duration()?.checked_sub(tolerance)?)
             .or_else(|| Some(self.genesis_slot()))
     }
-
-    /// Returns the delay between the start of the slot and when unaggregated attestations should be
-    /// produced.
-    fn unagg_attestation_production_delay(&self) -> Duration {
-        self.slot_duration() / 3
-    }
-
-    /// Returns the delay between the start of the slot and when aggregated attestations should be
-    /// produced.
-    fn agg_attestation_production_delay(&self) -> Duration {
-        self.slot_duration() * 2 / 3
-    }
 }
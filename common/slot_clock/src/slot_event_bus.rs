@@ -0,0 +1,296 @@
+//! Provides a bus which emits a `SlotEvent` at configurable points within each slot.
+//!
+//! Services which need to perform some duty at a particular time in the slot (e.g., producing
+//! attestations at 1/3 of the slot) should subscribe to the bus rather than computing their own
+//! sleep durations.
+use crate::SlotClock;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Notify};
+use tokio::time::{sleep, timeout};
+use types::Slot;
+
+/// The number of events which may be buffered for each subscriber before it starts to lag.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// A point within a slot at which a `SlotEvent` is emitted.
+///
+/// Offsets should always resolve to a duration which is less than the slot duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlotOffset {
+    /// The start of the slot.
+    Start,
+    /// `numerator / denominator` of the way through the slot.
+    Fraction { numerator: u32, denominator: u32 },
+    /// A fixed duration after the start of the slot.
+    Custom(Duration),
+}
+
+impl SlotOffset {
+    /// Returns the duration between the start of the slot and this offset.
+    pub fn delay(&self, slot_duration: Duration) -> Duration {
+        match self {
+            SlotOffset::Start => Duration::from_secs(0),
+            SlotOffset::Fraction {
+                numerator,
+                denominator,
+            } => (slot_duration * *numerator)
+                .checked_div(*denominator)
+                .unwrap_or_else(|| Duration::from_secs(0)),
+            SlotOffset::Custom(delay) => *delay,
+        }
+    }
+}
+
+/// Emitted by the `SlotEventBus` when `offset` has been reached during `slot`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlotEvent {
+    pub slot: Slot,
+    pub offset: SlotOffset,
+}
+
+struct Inner {
+    offsets: RwLock<Vec<SlotOffset>>,
+    tx: broadcast::Sender<SlotEvent>,
+    /// Wakes `SlotEventBus::run` when offsets are registered, so that it can reschedule.
+    offsets_changed: Notify,
+}
+
+/// Emits a `SlotEvent` to all subscribers each time one of the registered offsets is reached.
+///
+/// The bus does nothing until `Self::run` is spawned.
+#[derive(Clone)]
+pub struct SlotEventBus {
+    inner: Arc<Inner>,
+}
+
+impl Default for SlotEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlotEventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            inner: Arc::new(Inner {
+                offsets: RwLock::new(vec![]),
+                tx,
+                offsets_changed: Notify::new(),
+            }),
+        }
+    }
+
+    /// Register `offsets` with the bus and return a receiver for all future events.
+    ///
+    /// The receiver will see events for all offsets registered with the bus, not just `offsets`.
+    /// Subscribers should ignore events with offsets they are not interested in.
+    pub fn subscribe(&self, offsets: &[SlotOffset]) -> broadcast::Receiver<SlotEvent> {
+        let rx = self.inner.tx.subscribe();
+        let mut registered = self.inner.offsets.write();
+        let mut changed = false;
+        for offset in offsets {
+            if !registered.contains(offset) {
+                registered.push(*offset);
+                changed = true;
+            }
+        }
+        drop(registered);
+
+        if changed {
+            self.inner.offsets_changed.notify_one();
+        }
+        rx
+    }
+
+    /// Emit events to all subscribers. This future never resolves.
+    pub async fn run<S: SlotClock>(self, slot_clock: S) {
+        let slot_duration = slot_clock.slot_duration();
+        let mut last_event_time = None;
+
+        loop {
+            let now = if let Some(now) = slot_clock.now_duration() {
+                now
+            } else {
+                // If we can't read the slot clock, just wait another slot.
+                sleep(slot_duration).await;
+                continue;
+            };
+
+            let after = last_event_time.map_or(now, |last| std::cmp::max(now, last));
+            let (event, event_time) = if let Some(next) = self.next_event(&slot_clock, after) {
+                next
+            } else {
+                // No offsets have been registered yet, wait until some are.
+                self.inner.offsets_changed.notified().await;
+                continue;
+            };
+
+            let offsets_changed = timeout(
+                event_time.saturating_sub(now),
+                self.inner.offsets_changed.notified(),
+            )
+            .await
+            .is_ok();
+
+            // A newly registered offset may occur before `event`, so reschedule unless `event` is
+            // already due.
+            if offsets_changed
+                && slot_clock
+                    .now_duration()
+                    .map_or(false, |now| now < event_time)
+            {
+                continue;
+            }
+
+            // An error indicates that there are no subscribers, which is fine.
+            let _ = self.inner.tx.send(event);
+            last_event_time = Some(event_time);
+        }
+    }
+
+    /// Returns the first event which occurs strictly after `after` (a duration since the UNIX
+    /// epoch), along with the time at which it occurs.
+    fn next_event<S: SlotClock>(
+        &self,
+        slot_clock: &S,
+        after: Duration,
+    ) -> Option<(SlotEvent, Duration)> {
+        let slot_duration = slot_clock.slot_duration();
+        let current_slot = slot_clock
+            .slot_of(after)
+            .unwrap_or_else(|| slot_clock.genesis_slot());
+        let offsets = self.inner.offsets.read();
+
+        vec![current_slot, current_slot + 1]
+            .into_iter()
+            .flat_map(|slot| {
+                offsets.iter().filter_map(move |offset| {
+                    let event_time = slot_clock.start_of(slot)? + offset.delay(slot_duration);
+                    Some((
+                        SlotEvent {
+                            slot,
+                            offset: *offset,
+                        },
+                        event_time,
+                    ))
+                })
+            })
+            .filter(|(_, event_time)| *event_time > after)
+            .min_by_key(|(_, event_time)| *event_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ManualSlotClock;
+
+    const ONE_THIRD: SlotOffset = SlotOffset::Fraction {
+        numerator: 1,
+        denominator: 3,
+    };
+    const TWO_THIRDS: SlotOffset = SlotOffset::Fraction {
+        numerator: 2,
+        denominator: 3,
+    };
+
+    #[test]
+    fn offset_delay() {
+        let slot_duration = Duration::from_secs(12);
+
+        assert_eq!(
+            SlotOffset::Start.delay(slot_duration),
+            Duration::from_secs(0)
+        );
+        assert_eq!(ONE_THIRD.delay(slot_duration), Duration::from_secs(4));
+        assert_eq!(TWO_THIRDS.delay(slot_duration), Duration::from_secs(8));
+        assert_eq!(
+            SlotOffset::Custom(Duration::from_secs(3)).delay(slot_duration),
+            Duration::from_secs(3)
+        );
+    }
+
+    #[test]
+    fn next_event() {
+        let slot_duration = Duration::from_secs(12);
+        let clock = ManualSlotClock::new(Slot::new(0), Duration::from_secs(0), slot_duration);
+        let bus = SlotEventBus::new();
+        let _rx = bus.subscribe(&[TWO_THIRDS, ONE_THIRD]);
+
+        let next = |after: u64| bus.next_event(&clock, Duration::from_secs(after)).unwrap();
+
+        assert_eq!(
+            next(0),
+            (
+                SlotEvent {
+                    slot: Slot::new(0),
+                    offset: ONE_THIRD
+                },
+                Duration::from_secs(4)
+            )
+        );
+        assert_eq!(
+            next(4),
+            (
+                SlotEvent {
+                    slot: Slot::new(0),
+                    offset: TWO_THIRDS
+                },
+                Duration::from_secs(8)
+            )
+        );
+        assert_eq!(
+            next(8),
+            (
+                SlotEvent {
+                    slot: Slot::new(1),
+                    offset: ONE_THIRD
+                },
+                Duration::from_secs(16)
+            )
+        );
+    }
+
+    #[test]
+    fn no_offsets() {
+        let clock = ManualSlotClock::new(
+            Slot::new(0),
+            Duration::from_secs(0),
+            Duration::from_secs(12),
+        );
+        let bus = SlotEventBus::new();
+
+        assert!(bus.next_event(&clock, Duration::from_secs(0)).is_none());
+    }
+
+    #[tokio::test]
+    async fn subscribe_wakes_idle_bus() {
+        // With a long slot, the event is only emitted promptly if the bus is woken by `subscribe`.
+        let clock = ManualSlotClock::new(
+            Slot::new(0),
+            Duration::from_secs(0),
+            Duration::from_secs(600),
+        );
+        let bus = SlotEventBus::new();
+        tokio::spawn(bus.clone().run(clock));
+        tokio::task::yield_now().await;
+
+        let offset = SlotOffset::Custom(Duration::from_millis(10));
+        let mut rx = bus.subscribe(&[offset]);
+        let event = timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("event should be emitted without waiting for the next slot")
+            .expect("bus should not be closed");
+
+        assert_eq!(
+            event,
+            SlotEvent {
+                slot: Slot::new(0),
+                offset
+            }
+        );
+    }
+}
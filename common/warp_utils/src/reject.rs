@@ -91,6 +91,15 @@ pub fn custom_server_error(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(CustomServerError(msg))
 }
 
+#[derive(Debug)]
+pub struct CustomServiceUnavailable(pub String);
+
+impl Reject for CustomServiceUnavailable {}
+
+pub fn custom_service_unavailable(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(CustomServiceUnavailable(msg))
+}
+
 #[derive(Debug)]
 pub struct BroadcastWithoutImport(pub String);
 
@@ -188,6 +197,9 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::CustomServerError>() {
         code = StatusCode::INTERNAL_SERVER_ERROR;
         message = format!("INTERNAL_SERVER_ERROR: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::CustomServiceUnavailable>() {
+        code = StatusCode::SERVICE_UNAVAILABLE;
+        message = format!("SERVICE_UNAVAILABLE: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::BroadcastWithoutImport>() {
         code = StatusCode::ACCEPTED;
         message = format!(
@@ -5,6 +5,8 @@ use safe_arith::ArithError;
 
 const MAX_TREE_DEPTH: usize = 32;
 const EMPTY_SLICE: &[H256] = &[];
+/// Subtrees with at least this many leaves have their left and right branches hashed in parallel.
+const PARALLEL_HASHING_THRESHOLD: usize = 4_096;
 
 lazy_static! {
     /// Zero nodes to act as "synthetic" left and right subtrees of other zero nodes.
@@ -66,8 +68,17 @@ impl MerkleTree {
                     leaves.split_at(subtree_capacity)
                 };
 
-                let left_subtree = MerkleTree::create(left_leaves, depth - 1);
-                let right_subtree = MerkleTree::create(right_leaves, depth - 1);
+                let (left_subtree, right_subtree) = if leaves.len() >= PARALLEL_HASHING_THRESHOLD {
+                    rayon::join(
+                        || MerkleTree::create(left_leaves, depth - 1),
+                        || MerkleTree::create(right_leaves, depth - 1),
+                    )
+                } else {
+                    (
+                        MerkleTree::create(left_leaves, depth - 1),
+                        MerkleTree::create(right_leaves, depth - 1),
+                    )
+                };
                 let hash = H256::from_slice(&hash32_concat(
                     left_subtree.hash().as_bytes(),
                     right_subtree.hash().as_bytes(),
@@ -136,6 +147,69 @@ impl MerkleTree {
         Ok(())
     }
 
+    /// Push many elements into the MerkleTree, which must already contain exactly `num_leaves`
+    /// leaves.
+    ///
+    /// This is equivalent to calling `push_leaf` for each element, however each internal node is
+    /// only re-hashed once, rather than once per element.
+    pub fn push_leaves(
+        &mut self,
+        elems: &[H256],
+        num_leaves: usize,
+        depth: usize,
+    ) -> Result<(), MerkleTreeError> {
+        if depth == 0 {
+            return Err(MerkleTreeError::DepthTooSmall);
+        }
+
+        self.extend(elems, num_leaves, depth)
+    }
+
+    fn extend(
+        &mut self,
+        elems: &[H256],
+        num_leaves: usize,
+        depth: usize,
+    ) -> Result<(), MerkleTreeError> {
+        use MerkleTree::*;
+
+        if elems.is_empty() {
+            return Ok(());
+        }
+
+        if num_leaves.saturating_add(elems.len()) > 2usize.pow(depth as u32) {
+            return Err(MerkleTreeError::MerkleTreeFull);
+        }
+
+        match self {
+            Leaf(_) => return Err(MerkleTreeError::LeafReached),
+            Zero(_) => {
+                if num_leaves != 0 {
+                    return Err(MerkleTreeError::Invalid);
+                }
+                *self = MerkleTree::create(elems, depth);
+            }
+            Node(ref mut hash, ref mut left, ref mut right) => {
+                let subtree_capacity = 2usize.pow(depth as u32 - 1);
+                if num_leaves < subtree_capacity {
+                    // Fill the left subtree, then put any remaining elements in the right one.
+                    let split = std::cmp::min(subtree_capacity - num_leaves, elems.len());
+                    let (left_elems, right_elems) = elems.split_at(split);
+                    left.extend(left_elems, num_leaves, depth - 1)?;
+                    right.extend(right_elems, 0, depth - 1)?;
+                } else {
+                    right.extend(elems, num_leaves - subtree_capacity, depth - 1)?;
+                }
+                hash.assign_from_slice(&hash32_concat(
+                    left.hash().as_bytes(),
+                    right.hash().as_bytes(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Retrieve the root hash of this Merkle tree.
     pub fn hash(&self) -> H256 {
         match *self {
@@ -283,6 +357,40 @@ mod tests {
         TestResult::from_bool(proofs_ok)
     }
 
+    #[quickcheck]
+    fn quickcheck_push_leaves_matches_push_leaf(
+        int_leaves: Vec<u64>,
+        split: usize,
+        depth: usize,
+    ) -> TestResult {
+        if depth == 0 || depth > MAX_TREE_DEPTH || int_leaves.len() > 2usize.pow(depth as u32) {
+            return TestResult::discard();
+        }
+
+        let leaves: Vec<_> = int_leaves.into_iter().map(H256::from_low_u64_be).collect();
+        let split = split.checked_rem(leaves.len() + 1).unwrap_or(0);
+        let (initial, rest) = leaves.split_at(split);
+
+        let mut merkle_tree = MerkleTree::create(initial, depth);
+        assert_eq!(merkle_tree.push_leaves(rest, initial.len(), depth), Ok(()));
+
+        TestResult::from_bool(merkle_tree == MerkleTree::create(&leaves, depth))
+    }
+
+    #[test]
+    fn push_leaves_full() {
+        let depth = 1;
+        let leaf = H256::from([0xAA; 32]);
+        let mut tree = MerkleTree::create(&[leaf], depth);
+
+        assert_eq!(
+            tree.push_leaves(&[leaf, leaf], 1, depth),
+            Err(MerkleTreeError::MerkleTreeFull)
+        );
+        assert_eq!(tree.push_leaves(&[leaf], 1, depth), Ok(()));
+        assert_eq!(tree, MerkleTree::create(&[leaf, leaf], depth));
+    }
+
     #[test]
     fn sparse_zero_correct() {
         let depth = 2;
@@ -24,7 +24,7 @@ pub struct ProtoNode {
     pub parent: Option<usize>,
     pub justified_epoch: Epoch,
     pub finalized_epoch: Epoch,
-    weight: u64,
+    pub(crate) weight: u64,
     best_child: Option<usize>,
     best_descendant: Option<usize>,
 }
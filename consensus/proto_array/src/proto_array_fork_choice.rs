@@ -207,6 +207,16 @@ impl ProtoArrayForkChoice {
         })
     }
 
+    /// Returns the weight of the block with `block_root`, i.e., the total balance of the
+    /// validators whose latest message supports it or one of its descendants.
+    pub fn get_weight(&self, block_root: &Hash256) -> Option<u64> {
+        let block_index = self.proto_array.indices.get(block_root)?;
+        self.proto_array
+            .nodes
+            .get(*block_index)
+            .map(|node| node.weight)
+    }
+
     /// Returns `true` if the `descendant_root` has an ancestor with `ancestor_root`. Always
     /// returns `false` if either input roots are unknown.
     ///
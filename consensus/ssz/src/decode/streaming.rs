@@ -0,0 +1,178 @@
+use super::*;
+use std::io::Read;
+use std::marker::PhantomData;
+
+/// The lengths of the items in a list which are yet to be read.
+enum ItemLengths {
+    /// Each item has the same length, `len`.
+    Fixed { len: usize, remaining: usize },
+    /// The lengths of each item, derived from the offsets at the start of the list.
+    Variable(std::vec::IntoIter<usize>),
+}
+
+impl Iterator for ItemLengths {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match self {
+            ItemLengths::Fixed { len, remaining } => {
+                *remaining = remaining.checked_sub(1)?;
+                Some(*len)
+            }
+            ItemLengths::Variable(lengths) => lengths.next(),
+        }
+    }
+}
+
+/// Decodes a SSZ list of `T` from an `io::Read`, one item at a time.
+///
+/// This is equivalent to decoding a `Vec<T>` (or `VariableList<T, N>`) from a byte slice, except
+/// that at most one item is held in memory at a time. This allows large lists (e.g., a list of
+/// blocks) to be processed without first buffering all of their bytes.
+///
+/// Only the offsets are read when the decoder is created. Each subsequent call to `next` reads and
+/// decodes a single item. Once an error has been returned the iterator is fused and will only
+/// return `None`.
+///
+/// ## Example
+///
+/// ```rust
+/// use ssz::{Encode, StreamingDecoder};
+///
+/// let list: Vec<Vec<u16>> = vec![vec![1, 2], vec![], vec![3]];
+/// let bytes = list.as_ssz_bytes();
+///
+/// let decoder = StreamingDecoder::<_, Vec<u16>>::new(&bytes[..], bytes.len(), None).unwrap();
+/// assert_eq!(decoder.collect::<Result<Vec<_>, _>>().unwrap(), list);
+/// ```
+pub struct StreamingDecoder<R, T> {
+    reader: R,
+    item_lengths: ItemLengths,
+    /// Re-used between items to avoid an allocation per item.
+    buffer: Vec<u8>,
+    failed: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<R: Read, T: Decode> StreamingDecoder<R, T> {
+    /// Creates a decoder for a list of `T` which is encoded in the next `num_bytes` bytes of
+    /// `reader`.
+    ///
+    /// If `max_len` is `Some`, an error is returned if the list contains more than `max_len`
+    /// items.
+    pub fn new(
+        mut reader: R,
+        num_bytes: usize,
+        max_len: Option<usize>,
+    ) -> Result<Self, DecodeError> {
+        let item_lengths = if num_bytes == 0 {
+            ItemLengths::Fixed {
+                len: 0,
+                remaining: 0,
+            }
+        } else if T::is_ssz_fixed_len() {
+            let len = T::ssz_fixed_len();
+            if len == 0 {
+                return Err(DecodeError::ZeroLengthItem);
+            }
+            if num_bytes % len != 0 {
+                return Err(DecodeError::InvalidByteLength {
+                    len: num_bytes % len,
+                    expected: len,
+                });
+            }
+
+            let num_items = num_bytes / len;
+            check_max_len(num_items, max_len)?;
+
+            ItemLengths::Fixed {
+                len,
+                remaining: num_items,
+            }
+        } else {
+            let mut offset_bytes = [0; BYTES_PER_LENGTH_OFFSET];
+            read_exact(&mut reader, &mut offset_bytes)?;
+
+            let first_offset = read_offset(&offset_bytes)?;
+            sanitize_offset(first_offset, None, num_bytes, Some(first_offset))?;
+
+            if first_offset % BYTES_PER_LENGTH_OFFSET != 0 || first_offset < BYTES_PER_LENGTH_OFFSET
+            {
+                return Err(DecodeError::InvalidListFixedBytesLen(first_offset));
+            }
+
+            let num_items = first_offset / BYTES_PER_LENGTH_OFFSET;
+            check_max_len(num_items, max_len)?;
+
+            let mut remaining_offsets = vec![0; first_offset - BYTES_PER_LENGTH_OFFSET];
+            read_exact(&mut reader, &mut remaining_offsets)?;
+
+            let mut lengths = Vec::with_capacity(num_items);
+            let mut offset = first_offset;
+            for offset_bytes in remaining_offsets.chunks(BYTES_PER_LENGTH_OFFSET) {
+                let next_offset = sanitize_offset(
+                    read_offset(offset_bytes)?,
+                    Some(offset),
+                    num_bytes,
+                    Some(first_offset),
+                )?;
+                lengths.push(next_offset - offset);
+                offset = next_offset;
+            }
+            lengths.push(num_bytes - offset);
+
+            ItemLengths::Variable(lengths.into_iter())
+        };
+
+        Ok(Self {
+            reader,
+            item_lengths,
+            buffer: vec![],
+            failed: false,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn next_item(&mut self, len: usize) -> Result<T, DecodeError> {
+        self.buffer.resize(len, 0);
+        read_exact(&mut self.reader, &mut self.buffer)?;
+        T::from_ssz_bytes(&self.buffer)
+    }
+}
+
+impl<R: Read, T: Decode> Iterator for StreamingDecoder<R, T> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let len = self.item_lengths.next()?;
+        let result = self.next_item(len);
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+fn check_max_len(num_items: usize, max_len: Option<usize>) -> Result<(), DecodeError> {
+    if max_len.map_or(false, |max| num_items > max) {
+        Err(DecodeError::BytesInvalid(format!(
+            "Variable length list of {} items exceeds maximum of {:?}",
+            num_items, max_len
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), DecodeError> {
+    reader
+        .read_exact(buf)
+        .map_err(|e| DecodeError::BytesInvalid(format!("Failed to read SSZ bytes: {}", e)))
+}
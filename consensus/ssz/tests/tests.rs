@@ -384,3 +384,107 @@ mod round_trip {
         round_trip(vec);
     }
 }
+
+mod streaming {
+    use super::*;
+    use ssz::StreamingDecoder;
+
+    fn stream<T: Decode>(bytes: &[u8], max_len: Option<usize>) -> Result<Vec<T>, DecodeError> {
+        StreamingDecoder::new(bytes, bytes.len(), max_len)?.collect()
+    }
+
+    #[test]
+    fn matches_slice_decoding() {
+        let fixed: Vec<u16> = vec![1, 2, 3, 4];
+        assert_eq!(stream::<u16>(&fixed.as_ssz_bytes(), None), Ok(fixed));
+
+        let variable: Vec<Vec<u16>> = vec![vec![], vec![1, 2], vec![], vec![3, 4, 5]];
+        assert_eq!(
+            stream::<Vec<u16>>(&variable.as_ssz_bytes(), None),
+            Ok(variable)
+        );
+
+        assert_eq!(stream::<Vec<u16>>(&[], None), Ok(vec![]));
+    }
+
+    #[test]
+    fn reads_one_item_at_a_time() {
+        let list: Vec<Vec<u8>> = vec![vec![1; 10], vec![2; 20], vec![3; 30]];
+        let bytes = list.as_ssz_bytes();
+        let mut reader = &bytes[..];
+
+        let mut decoder = StreamingDecoder::<_, Vec<u8>>::new(&mut reader, bytes.len(), None)
+            .expect("should read offsets");
+        assert_eq!(decoder.next(), Some(Ok(vec![1; 10])));
+        assert_eq!(decoder.next(), Some(Ok(vec![2; 20])));
+        drop(decoder);
+
+        // Only the last item should remain unread.
+        assert_eq!(reader, &[3; 30][..]);
+    }
+
+    #[test]
+    fn does_not_consume_trailing_bytes() {
+        let list: Vec<Vec<u8>> = vec![vec![1, 2], vec![3]];
+        let mut bytes = list.as_ssz_bytes();
+        let len = bytes.len();
+        bytes.extend_from_slice(&[42, 42]);
+
+        let mut decoder = StreamingDecoder::<_, Vec<u8>>::new(&bytes[..], len, None).unwrap();
+        assert_eq!((&mut decoder).collect::<Result<Vec<_>, _>>(), Ok(list));
+        assert_eq!(decoder.into_inner(), &[42, 42][..]);
+    }
+
+    #[test]
+    fn enforces_max_len() {
+        let fixed: Vec<u16> = vec![1, 2, 3];
+        assert!(matches!(
+            stream::<u16>(&fixed.as_ssz_bytes(), Some(2)),
+            Err(DecodeError::BytesInvalid(_))
+        ));
+
+        let variable: Vec<Vec<u16>> = vec![vec![1], vec![2], vec![3]];
+        assert!(matches!(
+            stream::<Vec<u16>>(&variable.as_ssz_bytes(), Some(2)),
+            Err(DecodeError::BytesInvalid(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_offsets() {
+        // Third offset is before the second.
+        assert_eq!(
+            stream::<Vec<u8>>(&[12, 0, 0, 0, 14, 0, 0, 0, 13, 0, 0, 0, 1, 2, 3], None),
+            Err(DecodeError::OffsetsAreDecreasing(13))
+        );
+
+        // Second offset is beyond the end of the list.
+        assert_eq!(
+            stream::<Vec<u8>>(&[8, 0, 0, 0, 10, 0, 0, 0, 1], None),
+            Err(DecodeError::OffsetOutOfBounds(10))
+        );
+
+        // First offset is not a multiple of the offset length.
+        assert_eq!(
+            stream::<Vec<u8>>(&[5, 0, 0, 0, 1], None),
+            Err(DecodeError::InvalidListFixedBytesLen(5))
+        );
+    }
+
+    #[test]
+    fn truncated_reader() {
+        let list: Vec<Vec<u8>> = vec![vec![1, 2], vec![3, 4]];
+        let bytes = list.as_ssz_bytes();
+
+        // Claim the list is longer than the bytes which are available.
+        let mut decoder =
+            StreamingDecoder::<_, Vec<u8>>::new(&bytes[..bytes.len() - 1], bytes.len(), None)
+                .unwrap();
+        assert_eq!(decoder.next(), Some(Ok(vec![1, 2])));
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(DecodeError::BytesInvalid(_)))
+        ));
+        assert_eq!(decoder.next(), None);
+    }
+}
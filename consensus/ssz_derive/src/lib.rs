@@ -47,14 +47,49 @@ fn get_serializable_field_types(struct_data: &syn::DataStruct) -> Vec<&syn::Type
         .collect()
 }
 
+/// The flags which may be supplied in a `#[ssz(...)]` field attribute.
+const FIELD_FLAGS: &[&str] = &["skip_serializing", "skip_deserializing"];
+
+/// Returns the flags supplied to all `#[ssz(...)]` attributes of `field`. Multiple flags may be
+/// supplied to a single attribute, e.g., `#[ssz(skip_serializing, skip_deserializing)]`.
+///
+/// # Panics
+/// Any malformed `ssz` attribute or unknown flag will raise a panic at compile time.
+fn get_field_flags(field: &syn::Field) -> Vec<String> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("ssz"))
+        .flat_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.into_iter().collect::<Vec<_>>(),
+            _ => panic!("ssz field attributes must be of the form #[ssz(flag, ...)]"),
+        })
+        .map(|nested| {
+            let flag = match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+            if !FIELD_FLAGS.contains(&flag.as_str()) {
+                panic!(
+                    "unknown ssz field attribute, expected one of: {}",
+                    FIELD_FLAGS.join(", ")
+                );
+            }
+            flag
+        })
+        .collect()
+}
+
 /// Returns true if some field has an attribute declaring it should not be serialized.
 ///
 /// The field attribute is: `#[ssz(skip_serializing)]`
 fn should_skip_serializing(field: &syn::Field) -> bool {
-    field.attrs.iter().any(|attr| {
-        attr.path.is_ident("ssz")
-            && attr.tokens.to_string().replace(" ", "") == "(skip_serializing)"
-    })
+    get_field_flags(field)
+        .iter()
+        .any(|flag| flag == "skip_serializing")
 }
 
 /// Implements `ssz::Encode` for some `struct`.
@@ -64,6 +99,9 @@ fn should_skip_serializing(field: &syn::Field) -> bool {
 /// ## Field attributes
 ///
 /// - `#[ssz(skip_serializing)]`: the field will not be serialized.
+///
+/// A field which is neither serialized nor deserialized (e.g., a cache which can be rebuilt) should
+/// be annotated with `#[ssz(skip_serializing, skip_deserializing)]`.
 #[proc_macro_derive(Encode, attributes(ssz))]
 pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
@@ -156,10 +194,9 @@ pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
 ///
 /// The field attribute is: `#[ssz(skip_deserializing)]`
 fn should_skip_deserializing(field: &syn::Field) -> bool {
-    field.attrs.iter().any(|attr| {
-        attr.path.is_ident("ssz")
-            && attr.tokens.to_string().replace(" ", "") == "(skip_deserializing)"
-    })
+    get_field_flags(field)
+        .iter()
+        .any(|flag| flag == "skip_deserializing")
 }
 
 /// Implements `ssz::Decode` for some `struct`.
@@ -171,7 +208,7 @@ fn should_skip_deserializing(field: &syn::Field) -> bool {
 /// - `#[ssz(skip_deserializing)]`: during de-serialization the field will be instantiated from a
 /// `Default` implementation. The decoder will assume that the field was not serialized at all
 /// (e.g., if it has been serialized, an error will be raised instead of `Default` overriding it).
-#[proc_macro_derive(Decode)]
+#[proc_macro_derive(Decode, attributes(ssz))]
 pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
 
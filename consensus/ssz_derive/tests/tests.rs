@@ -0,0 +1,117 @@
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+
+fn assert_encode_decode<T: Encode + Decode + PartialEq + std::fmt::Debug>(item: &T, bytes: &[u8]) {
+    assert_eq!(item.as_ssz_bytes(), bytes);
+    assert_eq!(item.ssz_bytes_len(), bytes.len());
+    assert_eq!(T::from_ssz_bytes(bytes).unwrap(), *item);
+}
+
+#[derive(PartialEq, Debug, Encode, Decode)]
+struct Fixed {
+    a: u16,
+    b: u64,
+}
+
+#[derive(PartialEq, Debug, Encode, Decode)]
+struct FixedWithSkip {
+    a: u16,
+    #[ssz(skip_serializing, skip_deserializing)]
+    cache: Option<u32>,
+    b: u64,
+}
+
+#[derive(PartialEq, Debug, Encode, Decode)]
+struct Variable {
+    a: u16,
+    b: Vec<u8>,
+    c: u64,
+}
+
+#[derive(PartialEq, Debug, Encode, Decode)]
+struct VariableWithSkip {
+    a: u16,
+    #[ssz(skip_serializing, skip_deserializing)]
+    cache: Vec<u64>,
+    b: Vec<u8>,
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    other_cache: u8,
+    c: u64,
+}
+
+#[test]
+fn fixed_skipped_field_is_not_encoded() {
+    let plain = Fixed { a: 1, b: 42 };
+    let skipped = FixedWithSkip {
+        a: 1,
+        cache: None,
+        b: 42,
+    };
+
+    assert!(<FixedWithSkip as Decode>::is_ssz_fixed_len());
+    assert_eq!(
+        <FixedWithSkip as Decode>::ssz_fixed_len(),
+        <Fixed as Decode>::ssz_fixed_len()
+    );
+    assert_encode_decode(&skipped, &plain.as_ssz_bytes());
+}
+
+#[test]
+fn fixed_skipped_field_is_default_on_decode() {
+    let plain = Fixed { a: 1, b: 42 };
+    let skipped = FixedWithSkip {
+        a: 1,
+        cache: Some(7),
+        b: 42,
+    };
+
+    let bytes = skipped.as_ssz_bytes();
+    assert_eq!(bytes, plain.as_ssz_bytes());
+
+    let decoded = FixedWithSkip::from_ssz_bytes(&bytes).unwrap();
+    assert_eq!(decoded.cache, None);
+    assert_eq!((decoded.a, decoded.b), (skipped.a, skipped.b));
+}
+
+#[test]
+fn variable_skipped_fields_are_not_encoded() {
+    let plain = Variable {
+        a: 1,
+        b: vec![2, 3, 4],
+        c: 5,
+    };
+    let skipped = VariableWithSkip {
+        a: 1,
+        cache: vec![],
+        b: vec![2, 3, 4],
+        other_cache: 0,
+        c: 5,
+    };
+
+    assert!(!<VariableWithSkip as Decode>::is_ssz_fixed_len());
+    assert_encode_decode(&skipped, &plain.as_ssz_bytes());
+}
+
+#[test]
+fn variable_skipped_fields_are_default_on_decode() {
+    let skipped = VariableWithSkip {
+        a: 1,
+        cache: vec![6, 7],
+        b: vec![2, 3, 4],
+        other_cache: 8,
+        c: 5,
+    };
+
+    let decoded = VariableWithSkip::from_ssz_bytes(&skipped.as_ssz_bytes()).unwrap();
+    assert_eq!(
+        decoded,
+        VariableWithSkip {
+            a: 1,
+            cache: vec![],
+            b: vec![2, 3, 4],
+            other_cache: 0,
+            c: 5,
+        }
+    );
+}
@@ -2,8 +2,24 @@ use eth2_hashing::hash;
 use int_to_bytes::int_to_bytes32;
 use merkle_proof::{MerkleTree, MerkleTreeError};
 use safe_arith::SafeArith;
+use ssz_derive::{Decode, Encode};
 use types::Hash256;
 
+/// The root and size of a `DepositDataTree` at some point in time, e.g., as persisted to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct DepositTreeSnapshot {
+    pub deposit_root: Hash256,
+    pub deposit_count: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DepositTreeSnapshotError {
+    /// The number of deposits in the tree does not match the snapshot.
+    DepositCountMismatch { tree: u64, snapshot: u64 },
+    /// The root of the tree does not match the snapshot.
+    DepositRootMismatch { tree: Hash256, snapshot: Hash256 },
+}
+
 /// Emulates the eth1 deposit contract merkle tree.
 pub struct DepositDataTree {
     tree: MerkleTree,
@@ -50,4 +66,75 @@ impl DepositDataTree {
         self.mix_in_length.safe_add_assign(1)?;
         Ok(())
     }
+
+    /// Add many deposits to the merkle tree.
+    ///
+    /// This is equivalent to calling `push_leaf` for each leaf, but is much faster when loading a
+    /// large number of deposits since each internal node is only hashed once.
+    pub fn push_leaves(&mut self, leaves: &[Hash256]) -> Result<(), MerkleTreeError> {
+        self.tree
+            .push_leaves(leaves, self.mix_in_length, self.depth)?;
+        self.mix_in_length.safe_add_assign(leaves.len())?;
+        Ok(())
+    }
+
+    /// Returns the current root and deposit count of the tree.
+    pub fn snapshot(&self) -> DepositTreeSnapshot {
+        DepositTreeSnapshot {
+            deposit_root: self.root(),
+            deposit_count: self.mix_in_length as u64,
+        }
+    }
+
+    /// Check that the tree has the same deposit count and root as `snapshot`.
+    ///
+    /// Used to detect corruption when a tree is rebuilt from persisted leaves.
+    pub fn verify_against_snapshot(
+        &self,
+        snapshot: &DepositTreeSnapshot,
+    ) -> Result<(), DepositTreeSnapshotError> {
+        let tree = self.snapshot();
+        if tree.deposit_count != snapshot.deposit_count {
+            Err(DepositTreeSnapshotError::DepositCountMismatch {
+                tree: tree.deposit_count,
+                snapshot: snapshot.deposit_count,
+            })
+        } else if tree.deposit_root != snapshot.deposit_root {
+            Err(DepositTreeSnapshotError::DepositRootMismatch {
+                tree: tree.deposit_root,
+                snapshot: snapshot.deposit_root,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::DEPOSIT_TREE_DEPTH;
+
+    #[test]
+    fn push_leaves_matches_push_leaf() {
+        let leaves = (0..100).map(Hash256::from_low_u64_be).collect::<Vec<_>>();
+
+        let mut incremental = DepositDataTree::create(&[], 0, DEPOSIT_TREE_DEPTH);
+        for leaf in &leaves {
+            incremental.push_leaf(*leaf).expect("should push leaf");
+        }
+
+        // Extend a non-empty tree in uneven batches to exercise partially-filled subtrees.
+        let mut bulk = DepositDataTree::create(&leaves[..3], 3, DEPOSIT_TREE_DEPTH);
+        bulk.push_leaves(&leaves[3..37])
+            .expect("should push leaves");
+        bulk.push_leaves(&[]).expect("should push no leaves");
+        bulk.push_leaves(&leaves[37..]).expect("should push leaves");
+
+        assert_eq!(bulk.snapshot(), incremental.snapshot());
+        assert_eq!(
+            bulk.snapshot(),
+            DepositDataTree::create(&leaves, leaves.len(), DEPOSIT_TREE_DEPTH).snapshot()
+        );
+    }
 }
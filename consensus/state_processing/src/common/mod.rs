@@ -5,7 +5,7 @@ mod get_indexed_attestation;
 mod initiate_validator_exit;
 mod slash_validator;
 
-pub use deposit_data_tree::DepositDataTree;
+pub use deposit_data_tree::{DepositDataTree, DepositTreeSnapshot, DepositTreeSnapshotError};
 pub use get_attesting_indices::get_attesting_indices;
 pub use get_base_reward::get_base_reward;
 pub use get_indexed_attestation::get_indexed_attestation;
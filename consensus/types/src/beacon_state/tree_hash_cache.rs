@@ -164,6 +164,34 @@ impl<T: EthSpec> BeaconTreeHashCache<T> {
             }
         }
 
+        // The validators, balances and pending attestations are by far the largest fields, so
+        // their roots are computed in parallel. Each of the cached fields has its own arena, so
+        // they can be borrowed independently of the rest of `self`.
+        let validators_cache = &mut self.validators;
+        let balances_arena = &mut self.balances_arena;
+        let balances_cache = &mut self.balances;
+        let (
+            validators_root,
+            (balances_root, (previous_attestations_root, current_attestations_root)),
+        ) = rayon::join(
+            || validators_cache.recalculate_tree_hash_root(&state.validators[..]),
+            || {
+                rayon::join(
+                    || {
+                        state
+                            .balances
+                            .recalculate_tree_hash_root(balances_arena, balances_cache)
+                    },
+                    || {
+                        rayon::join(
+                            || state.previous_epoch_attestations.tree_hash_root(),
+                            || state.current_epoch_attestations.tree_hash_root(),
+                        )
+                    },
+                )
+            },
+        );
+
         let mut hasher = MerkleHasher::with_leaves(NUM_BEACON_STATE_HASHING_FIELDS);
 
         hasher.write(state.genesis_time.tree_hash_root().as_bytes())?;
@@ -196,17 +224,8 @@ impl<T: EthSpec> BeaconTreeHashCache<T> {
                 .as_bytes(),
         )?;
         hasher.write(state.eth1_deposit_index.tree_hash_root().as_bytes())?;
-        hasher.write(
-            self.validators
-                .recalculate_tree_hash_root(&state.validators[..])?
-                .as_bytes(),
-        )?;
-        hasher.write(
-            state
-                .balances
-                .recalculate_tree_hash_root(&mut self.balances_arena, &mut self.balances)?
-                .as_bytes(),
-        )?;
+        hasher.write(validators_root?.as_bytes())?;
+        hasher.write(balances_root?.as_bytes())?;
         hasher.write(
             state
                 .randao_mixes
@@ -219,13 +238,8 @@ impl<T: EthSpec> BeaconTreeHashCache<T> {
                 .recalculate_tree_hash_root(&mut self.slashings_arena, &mut self.slashings)?
                 .as_bytes(),
         )?;
-        hasher.write(
-            state
-                .previous_epoch_attestations
-                .tree_hash_root()
-                .as_bytes(),
-        )?;
-        hasher.write(state.current_epoch_attestations.tree_hash_root().as_bytes())?;
+        hasher.write(previous_attestations_root.as_bytes())?;
+        hasher.write(current_attestations_root.as_bytes())?;
         hasher.write(state.justification_bits.tree_hash_root().as_bytes())?;
         hasher.write(
             state
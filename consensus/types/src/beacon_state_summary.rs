@@ -0,0 +1,134 @@
+use crate::test_utils::TestRandom;
+use crate::{BeaconState, BeaconStateError, ChainSpec, Checkpoint, EthSpec, RelativeEpoch, Slot};
+use safe_arith::SafeArith;
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use std::collections::HashSet;
+use test_random_derive::TestRandom;
+
+/// The number of validators in a `BeaconState` with each status, as of the current epoch of the
+/// state.
+///
+/// Each validator is counted against exactly one of `pending`, `active`, `exited` or
+/// `withdrawable`. Slashed validators are also counted in `slashed`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, Encode, Decode, TestRandom,
+)]
+pub struct ValidatorCounts {
+    /// Validators which have not yet been activated.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub pending: u64,
+    /// Validators which are active, including those which are slashed but not yet exited.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub active: u64,
+    /// Validators which have exited but are not yet withdrawable.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub exited: u64,
+    /// Validators which are withdrawable.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub withdrawable: u64,
+    /// Validators which have been slashed, regardless of their other status.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slashed: u64,
+}
+
+/// A compact summary of a `BeaconState`, for use by APIs and monitoring tools which do not
+/// require the full state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TestRandom)]
+pub struct BeaconStateSummary {
+    pub slot: Slot,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub validator_counts: ValidatorCounts,
+    /// The sum of the balances of all validators.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total_balance: u64,
+    /// The sum of the effective balances of all validators which are active in the current epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total_active_balance: u64,
+    /// The sum of the effective balances of all validators whose attestations for the current
+    /// epoch are included in the state.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub current_epoch_participating_balance: u64,
+}
+
+impl BeaconStateSummary {
+    /// Summarise `state`.
+    ///
+    /// Note: Utilizes the cache and will fail if the current epoch committee cache is not
+    /// initialized.
+    pub fn from_state<E: EthSpec>(
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<Self, BeaconStateError> {
+        let current_epoch = state.current_epoch();
+
+        let mut validator_counts = ValidatorCounts::default();
+        let mut total_active_balance = 0_u64;
+        for validator in state.validators.iter() {
+            let count = if validator.is_withdrawable_at(current_epoch) {
+                &mut validator_counts.withdrawable
+            } else if validator.is_exited_at(current_epoch) {
+                &mut validator_counts.exited
+            } else if validator.is_active_at(current_epoch) {
+                total_active_balance.safe_add_assign(validator.effective_balance)?;
+                &mut validator_counts.active
+            } else {
+                &mut validator_counts.pending
+            };
+            count.safe_add_assign(1)?;
+
+            if validator.slashed {
+                validator_counts.slashed.safe_add_assign(1)?;
+            }
+        }
+
+        let total_balance = state
+            .balances
+            .iter()
+            .try_fold(0_u64, |acc, balance| acc.safe_add(*balance))?;
+
+        let committee_cache = state.committee_cache(RelativeEpoch::Current)?;
+        let mut participants = HashSet::new();
+        for attestation in state.current_epoch_attestations.iter() {
+            let committee = committee_cache
+                .get_beacon_committee(attestation.data.slot, attestation.data.index)
+                .ok_or(BeaconStateError::NoCommittee {
+                    slot: attestation.data.slot,
+                    index: attestation.data.index,
+                })?;
+            if attestation.aggregation_bits.len() != committee.committee.len() {
+                return Err(BeaconStateError::InvalidBitfield);
+            }
+            participants.extend(
+                committee
+                    .committee
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| attestation.aggregation_bits.get(*i).unwrap_or(false))
+                    .map(|(_, validator_index)| *validator_index),
+            );
+        }
+        let participants = participants.into_iter().collect::<Vec<_>>();
+        let current_epoch_participating_balance = state.get_total_balance(&participants, spec)?;
+
+        Ok(Self {
+            slot: state.slot,
+            previous_justified_checkpoint: state.previous_justified_checkpoint,
+            current_justified_checkpoint: state.current_justified_checkpoint,
+            finalized_checkpoint: state.finalized_checkpoint,
+            validator_counts,
+            total_balance,
+            total_active_balance,
+            current_epoch_participating_balance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ssz_tests!(BeaconStateSummary);
+}
@@ -4,6 +4,7 @@ use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::path::Path;
+use std::time::Duration;
 use tree_hash::TreeHash;
 
 /// Each of the BLS signature domains.
@@ -74,6 +75,12 @@ pub struct ChainSpec {
     pub min_epochs_to_inactivity_penalty: u64,
     pub min_validator_withdrawability_delay: Epoch,
     pub shard_committee_period: u64,
+    /// The delay after the start of a slot at which unaggregated attestations are produced, in
+    /// milliseconds. If `None`, attestations are produced one third of the way through the slot.
+    pub attestation_production_delay_millis: Option<u64>,
+    /// The delay after the start of a slot at which aggregate attestations are produced, in
+    /// milliseconds. If `None`, aggregates are produced two thirds of the way through the slot.
+    pub aggregate_production_delay_millis: Option<u64>,
 
     /*
      * Reward and penalty quotients
@@ -123,6 +130,53 @@ pub struct ChainSpec {
 }
 
 impl ChainSpec {
+    /// Returns the `maximum_gossip_clock_disparity_millis` as a `Duration`.
+    pub fn maximum_gossip_clock_disparity(&self) -> Duration {
+        Duration::from_millis(self.maximum_gossip_clock_disparity_millis)
+    }
+
+    /// Returns the `seconds_per_slot` as a `Duration`.
+    pub fn slot_duration(&self) -> Duration {
+        Duration::from_secs(self.seconds_per_slot)
+    }
+
+    /// Returns the delay between the start of the slot and when unaggregated attestations should be
+    /// produced.
+    pub fn unagg_attestation_production_delay(&self) -> Duration {
+        self.attestation_production_delay_millis
+            .map_or_else(|| self.slot_duration() / 3, Duration::from_millis)
+    }
+
+    /// Returns the delay between the start of the slot and when aggregated attestations should be
+    /// produced.
+    pub fn agg_attestation_production_delay(&self) -> Duration {
+        self.aggregate_production_delay_millis
+            .map_or_else(|| self.slot_duration() * 2 / 3, Duration::from_millis)
+    }
+
+    /// Returns an error if the attestation or aggregate production delays do not fall within the
+    /// slot, in that order.
+    pub fn verify_attestation_production_delays(&self) -> Result<(), String> {
+        let attestation_delay = self.unagg_attestation_production_delay();
+        let aggregate_delay = self.agg_attestation_production_delay();
+
+        if aggregate_delay >= self.slot_duration() {
+            Err(format!(
+                "Aggregate production delay ({:?}) must be less than the slot duration ({:?})",
+                aggregate_delay,
+                self.slot_duration()
+            ))
+        } else if attestation_delay >= aggregate_delay {
+            Err(format!(
+                "Attestation production delay ({:?}) must be less than the aggregate production \
+                 delay ({:?})",
+                attestation_delay, aggregate_delay
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Returns an `EnrForkId` for the given `slot`.
     ///
     /// Presently, we don't have any forks so we just ignore the slot. In the future this function
@@ -287,6 +341,8 @@ impl ChainSpec {
             min_epochs_to_inactivity_penalty: 4,
             min_validator_withdrawability_delay: Epoch::new(256),
             shard_committee_period: 256,
+            attestation_production_delay_millis: None,
+            aggregate_production_delay_millis: None,
 
             /*
              * Reward and penalty quotients
@@ -447,6 +503,40 @@ mod tests {
             &spec,
         );
     }
+
+    #[test]
+    fn attestation_production_delays() {
+        let mut spec = ChainSpec::mainnet();
+        assert_eq!(
+            spec.unagg_attestation_production_delay(),
+            Duration::from_secs(4)
+        );
+        assert_eq!(
+            spec.agg_attestation_production_delay(),
+            Duration::from_secs(8)
+        );
+        assert!(spec.verify_attestation_production_delays().is_ok());
+
+        spec.seconds_per_slot = 2;
+        spec.attestation_production_delay_millis = Some(500);
+        spec.aggregate_production_delay_millis = Some(1_000);
+        assert_eq!(
+            spec.unagg_attestation_production_delay(),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            spec.agg_attestation_production_delay(),
+            Duration::from_millis(1_000)
+        );
+        assert!(spec.verify_attestation_production_delays().is_ok());
+
+        spec.attestation_production_delay_millis = Some(1_000);
+        assert!(spec.verify_attestation_production_delays().is_err());
+
+        spec.attestation_production_delay_millis = None;
+        spec.aggregate_production_delay_millis = Some(2_000);
+        assert!(spec.verify_attestation_production_delays().is_err());
+    }
 }
 
 /// YAML config file as defined by the spec.
@@ -575,6 +665,11 @@ pub struct YamlConfig {
     #[serde(with = "serde_utils::quoted_u64")]
     deposit_network_id: u64,
     deposit_contract_address: Address,
+    // Lighthouse-specific intra-slot timing, for networks with non-standard slot timings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attestation_production_delay_millis: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aggregate_production_delay_millis: Option<u64>,
 
     // Extra fields (could be from a future hard-fork that we don't yet know).
     #[serde(flatten)]
@@ -674,6 +769,8 @@ impl YamlConfig {
             deposit_chain_id: spec.deposit_chain_id,
             deposit_network_id: spec.deposit_network_id,
             deposit_contract_address: spec.deposit_contract_address,
+            attestation_production_delay_millis: spec.attestation_production_delay_millis,
+            aggregate_production_delay_millis: spec.aggregate_production_delay_millis,
 
             extra_fields: HashMap::new(),
         }
@@ -761,6 +858,8 @@ impl YamlConfig {
             ),
             shard_committee_period: self.shard_committee_period,
             min_epochs_to_inactivity_penalty: self.min_epochs_to_inactivity_penalty,
+            attestation_production_delay_millis: self.attestation_production_delay_millis,
+            aggregate_production_delay_millis: self.aggregate_production_delay_millis,
             /*
              * Reward and penalty quotients
              */
@@ -898,4 +997,25 @@ mod yaml_tests {
             .expect("should have applied spec");
         assert_eq!(new_spec, ChainSpec::minimal());
     }
+
+    #[test]
+    fn attestation_production_delays_round_trip() {
+        let mut spec = ChainSpec::minimal();
+        let yamlconfig = YamlConfig::from_spec::<MinimalEthSpec>(&spec);
+        let yaml = serde_yaml::to_string(&yamlconfig).expect("should serialize");
+        assert!(!yaml.contains("ATTESTATION_PRODUCTION_DELAY_MILLIS"));
+
+        spec.attestation_production_delay_millis = Some(1_000);
+        spec.aggregate_production_delay_millis = Some(2_000);
+        let yamlconfig = YamlConfig::from_spec::<MinimalEthSpec>(&spec);
+        let yaml = serde_yaml::to_string(&yamlconfig).expect("should serialize");
+        let from: YamlConfig = serde_yaml::from_str(&yaml).expect("should deserialize");
+        assert_eq!(from, yamlconfig);
+        assert!(from.extra_fields.is_empty());
+
+        let new_spec = from
+            .apply_to_chain_spec::<MinimalEthSpec>(&ChainSpec::minimal())
+            .expect("should have applied spec");
+        assert_eq!(new_spec, spec);
+    }
 }
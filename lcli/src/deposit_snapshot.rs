@@ -0,0 +1,148 @@
+//! Tools for debugging `DepositTreeSnapshot`s, as used to verify the deposit tree which is
+//! rebuilt from the eth1 deposit cache.
+//!
+//! Snapshots are read and written as SSZ. Deposit data roots are read from a text file containing
+//! one 0x-prefixed hex root per line, in deposit index order.
+use clap::ArgMatches;
+use ssz::{Decode, Encode};
+use state_processing::common::{DepositDataTree, DepositTreeSnapshot};
+use std::fs;
+use std::path::PathBuf;
+use types::{Hash256, DEPOSIT_TREE_DEPTH};
+
+pub fn run(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        ("decode", Some(matches)) => decode(matches),
+        ("validate", Some(matches)) => validate(matches),
+        ("diff", Some(matches)) => diff(matches),
+        ("synthesize", Some(matches)) => synthesize(matches),
+        (other, _) => Err(format!(
+            "Unknown deposit-snapshot subcommand {}. See --help.",
+            other
+        )),
+    }
+}
+
+fn decode(matches: &ArgMatches) -> Result<(), String> {
+    let snapshot = read_snapshot(matches, "snapshot")?;
+    print_snapshot(&snapshot);
+    Ok(())
+}
+
+/// Rebuild the deposit tree from the deposit data roots and check it matches the snapshot.
+fn validate(matches: &ArgMatches) -> Result<(), String> {
+    let snapshot = read_snapshot(matches, "snapshot")?;
+    let leaves = read_deposit_roots(matches)?;
+
+    if (leaves.len() as u64) < snapshot.deposit_count {
+        return Err(format!(
+            "Snapshot contains {} deposits but only {} deposit data roots were supplied",
+            snapshot.deposit_count,
+            leaves.len()
+        ));
+    }
+
+    let leaves = &leaves[..snapshot.deposit_count as usize];
+    let tree = DepositDataTree::create(leaves, leaves.len(), DEPOSIT_TREE_DEPTH);
+    tree.verify_against_snapshot(&snapshot)
+        .map_err(|e| format!("Snapshot is invalid: {:?}", e))?;
+
+    println!("Snapshot is valid");
+    print_snapshot(&snapshot);
+    Ok(())
+}
+
+fn diff(matches: &ArgMatches) -> Result<(), String> {
+    let a = read_snapshot(matches, "snapshot-a")?;
+    let b = read_snapshot(matches, "snapshot-b")?;
+
+    if a == b {
+        println!("Snapshots are identical");
+        return Ok(());
+    }
+
+    if a.deposit_count != b.deposit_count {
+        println!("deposit_count: {} != {}", a.deposit_count, b.deposit_count);
+    }
+    if a.deposit_root != b.deposit_root {
+        println!("deposit_root: {:?} != {:?}", a.deposit_root, b.deposit_root);
+    }
+
+    Err("Snapshots differ".to_string())
+}
+
+fn synthesize(matches: &ArgMatches) -> Result<(), String> {
+    let mut leaves = read_deposit_roots(matches)?;
+    let output = parse_path(matches, "output")?;
+
+    if let Some(count) = matches.value_of("count") {
+        let count = count
+            .parse::<usize>()
+            .map_err(|e| format!("Unable to parse count: {}", e))?;
+        if count > leaves.len() {
+            return Err(format!(
+                "Cannot synthesize a snapshot of {} deposits from {} deposit data roots",
+                count,
+                leaves.len()
+            ));
+        }
+        leaves.truncate(count);
+    }
+
+    let snapshot = DepositDataTree::create(&leaves, leaves.len(), DEPOSIT_TREE_DEPTH).snapshot();
+    fs::write(&output, snapshot.as_ssz_bytes())
+        .map_err(|e| format!("Unable to write {:?}: {}", output, e))?;
+
+    print_snapshot(&snapshot);
+    Ok(())
+}
+
+fn print_snapshot(snapshot: &DepositTreeSnapshot) {
+    println!("deposit_root: {:?}", snapshot.deposit_root);
+    println!("deposit_count: {}", snapshot.deposit_count);
+}
+
+fn parse_path(matches: &ArgMatches, name: &str) -> Result<PathBuf, String> {
+    matches
+        .value_of(name)
+        .ok_or_else(|| format!("{} not specified", name))?
+        .parse::<PathBuf>()
+        .map_err(|e| format!("Unable to parse {}: {}", name, e))
+}
+
+fn read_snapshot(matches: &ArgMatches, name: &str) -> Result<DepositTreeSnapshot, String> {
+    let path = parse_path(matches, name)?;
+    let bytes = fs::read(&path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+    DepositTreeSnapshot::from_ssz_bytes(&bytes)
+        .map_err(|e| format!("Unable to decode snapshot {:?}: {:?}", path, e))
+}
+
+fn read_deposit_roots(matches: &ArgMatches) -> Result<Vec<Hash256>, String> {
+    let path = parse_path(matches, "deposit-roots")?;
+    let contents =
+        fs::read_to_string(&path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let bytes = line
+                .strip_prefix("0x")
+                .ok_or_else(|| format!("Deposit data root {} is not 0x-prefixed", i))
+                .and_then(|hex| {
+                    hex::decode(hex)
+                        .map_err(|e| format!("Invalid deposit data root {}: {:?}", i, e))
+                })?;
+            if bytes.len() != 32 {
+                return Err(format!(
+                    "Deposit data root {} is {} bytes, expected 32",
+                    i,
+                    bytes.len()
+                ));
+            }
+            Ok(Hash256::from_slice(&bytes))
+        })
+        .collect()
+}
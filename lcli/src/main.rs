@@ -3,6 +3,7 @@ extern crate log;
 mod change_genesis_time;
 mod check_deposit_data;
 mod deploy_deposit_contract;
+mod deposit_snapshot;
 mod eth1_genesis;
 mod generate_bootnode_enr;
 mod insecure_validators;
@@ -507,6 +508,101 @@ fn main() {
                         .help("The number of nodes to divide the validator keys to"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("deposit-snapshot")
+                .about("Inspects, validates and synthesizes SSZ-encoded deposit tree snapshots.")
+                .subcommand(
+                    SubCommand::with_name("decode")
+                        .about("Prints the contents of a snapshot.")
+                        .arg(
+                            Arg::with_name("snapshot")
+                                .index(1)
+                                .value_name("SSZ_FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Path to the SSZ-encoded snapshot"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("validate")
+                        .about(
+                            "Recomputes the deposit root from a list of deposit data roots and \
+                            checks it matches a snapshot.",
+                        )
+                        .arg(
+                            Arg::with_name("snapshot")
+                                .index(1)
+                                .value_name("SSZ_FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Path to the SSZ-encoded snapshot"),
+                        )
+                        .arg(
+                            Arg::with_name("deposit-roots")
+                                .long("deposit-roots")
+                                .value_name("FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help(
+                                    "Path to a file containing one 0x-prefixed deposit data \
+                                    root per line, in deposit index order",
+                                ),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("diff")
+                        .about("Prints the differences between two snapshots.")
+                        .arg(
+                            Arg::with_name("snapshot-a")
+                                .index(1)
+                                .value_name("SSZ_FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Path to the first SSZ-encoded snapshot"),
+                        )
+                        .arg(
+                            Arg::with_name("snapshot-b")
+                                .index(2)
+                                .value_name("SSZ_FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Path to the second SSZ-encoded snapshot"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("synthesize")
+                        .about("Creates a snapshot from a list of deposit data roots.")
+                        .arg(
+                            Arg::with_name("deposit-roots")
+                                .long("deposit-roots")
+                                .value_name("FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help(
+                                    "Path to a file containing one 0x-prefixed deposit data \
+                                    root per line, in deposit index order",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("count")
+                                .long("count")
+                                .value_name("COUNT")
+                                .takes_value(true)
+                                .help(
+                                    "Only include the first COUNT deposits. Defaults to all \
+                                    deposits in the file.",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .long("output")
+                                .value_name("SSZ_FILE")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Path to write the SSZ-encoded snapshot"),
+                        ),
+                ),
+        )
         .get_matches();
 
     let result = matches
@@ -607,6 +703,8 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run generate-bootnode-enr command: {}", e)),
         ("insecure-validators", Some(matches)) => insecure_validators::run(matches)
             .map_err(|e| format!("Failed to run insecure-validators command: {}", e)),
+        ("deposit-snapshot", Some(matches)) => deposit_snapshot::run(matches)
+            .map_err(|e| format!("Failed to run deposit-snapshot command: {}", e)),
         (other, _) => Err(format!("Unknown subcommand {}. See --help.", other)),
     }
 }
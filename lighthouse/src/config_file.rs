@@ -0,0 +1,225 @@
+//! Provides the `--config-file` flag and the `dump-config` subcommand, which allow the CLI flags for
+//! a Lighthouse process to be read from and written to a TOML or YAML file.
+//!
+//! Values supplied on the command line always take precedence over those in the config file. Any
+//! flag which is not supplied in either location takes its default value.
+use clap::{App, Arg, ArgMatches};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+
+pub const CONFIG_FILE_FLAG: &str = "config-file";
+pub const DUMP_CONFIG_SUBCOMMAND: &str = "dump-config";
+
+/// Flags which control the handling of config files (or are for testing only) and therefore may
+/// not appear inside one.
+const DISALLOWED_FLAGS: &[&str] = &[CONFIG_FILE_FLAG, "dump-config", "immediate-shutdown"];
+
+/// The value of a single flag in a config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FlagValue {
+    /// A flag which takes no value. `true` indicates that the flag is present.
+    Switch(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+/// A map of flag long-names (without the leading `--`) to their values.
+pub type Flags = BTreeMap<String, FlagValue>;
+
+enum Format {
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(Format::Toml),
+            Some("yml") | Some("yaml") => Ok(Format::Yaml),
+            _ => Err(format!(
+                "Config file {:?} must have a .toml, .yml or .yaml extension",
+                path
+            )),
+        }
+    }
+}
+
+/// Returns the `dump-config` subcommand, which is nested beneath the subcommand whose flags it
+/// writes (e.g., `lighthouse bn dump-config bn.toml`).
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(DUMP_CONFIG_SUBCOMMAND)
+        .about(
+            "Writes all explicitly supplied CLI flags (including those from --config-file) to a \
+            TOML (.toml) or YAML (.yml, .yaml) file which is suitable for use with --config-file, \
+            then exits.",
+        )
+        .arg(
+            Arg::with_name("path")
+                .value_name("FILE")
+                .help("Path of the config file to write.")
+                .required(true)
+                .takes_value(true)
+                .index(1),
+        )
+}
+
+/// Returns the matches for the top-level subcommand in `matches` (e.g., `beacon_node`), or
+/// `matches` itself if no subcommand was supplied.
+pub fn command_matches<'a, 'b>(matches: &'b ArgMatches<'a>) -> &'b ArgMatches<'a> {
+    match matches.subcommand() {
+        (_, Some(sub_matches)) => sub_matches,
+        _ => matches,
+    }
+}
+
+/// Reads the flags from the file at `path`.
+pub fn read_flags(path: &Path) -> Result<Flags, String> {
+    let format = Format::from_path(path)?;
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read config file {:?}: {:?}", path, e))?;
+
+    let flags: Flags = match format {
+        Format::Toml => toml::from_str(&contents)
+            .map_err(|e| format!("Unable to parse TOML config file {:?}: {:?}", path, e))?,
+        Format::Yaml => serde_yaml::from_str(&contents)
+            .map_err(|e| format!("Unable to parse YAML config file {:?}: {:?}", path, e))?,
+    };
+
+    if let Some(flag) = flags
+        .keys()
+        .find(|flag| DISALLOWED_FLAGS.contains(&flag.as_str()))
+    {
+        return Err(format!(
+            "The --{} flag cannot be set in a config file",
+            flag
+        ));
+    }
+
+    Ok(flags)
+}
+
+/// Writes `flags` to the file at `path`, using the format indicated by the file extension.
+pub fn write_flags(path: &Path, flags: &Flags) -> Result<(), String> {
+    let contents = match Format::from_path(path)? {
+        Format::Toml => toml::to_string(flags)
+            .map_err(|e| format!("Unable to serialize config file as TOML: {:?}", e))?,
+        Format::Yaml => serde_yaml::to_string(flags)
+            .map_err(|e| format!("Unable to serialize config file as YAML: {:?}", e))?,
+    };
+
+    fs::write(path, contents)
+        .map_err(|e| format!("Unable to write config file {:?}: {:?}", path, e))
+}
+
+/// Converts a single flag to CLI arguments.
+fn flag_to_args(flag: &str, value: &FlagValue) -> Vec<OsString> {
+    let flag = format!("--{}", flag).into();
+
+    match value {
+        FlagValue::Switch(true) => vec![flag],
+        // A disabled switch is equivalent to omitting the flag.
+        FlagValue::Switch(false) => vec![],
+        FlagValue::Integer(i) => vec![flag, i.to_string().into()],
+        FlagValue::Float(f) => vec![flag, f.to_string().into()],
+        FlagValue::String(s) => vec![flag, s.into()],
+    }
+}
+
+/// If `--config-file` was supplied, returns the process arguments extended with the flags from the
+/// config file. Returns `None` if no config file was supplied.
+///
+/// Each flag is placed next to the command it belongs to: flags accepted by the top-level
+/// `lighthouse` command (as determined by `parses_at_top_level`, which is given the arguments
+/// for a single flag) are inserted directly after the program name, whilst all other flags are
+/// added to the end of the subcommand's arguments, ahead of any `dump-config` subcommand. Any flag
+/// which was supplied on the command line is not duplicated, so the command line takes precedence
+/// over the config file.
+pub fn args_with_config_file<F>(
+    matches: &ArgMatches,
+    parses_at_top_level: F,
+) -> Result<Option<Vec<OsString>>, String>
+where
+    F: Fn(&[OsString]) -> bool,
+{
+    let sub_matches = command_matches(matches);
+
+    let path = if let Some(path) = sub_matches.value_of(CONFIG_FILE_FLAG) {
+        Path::new(path)
+    } else {
+        return Ok(None);
+    };
+
+    let flags = read_flags(path)?;
+    let mut args = std::env::args_os().collect::<Vec<_>>();
+    let program = args.first().cloned().unwrap_or_else(|| "lighthouse".into());
+
+    let mut top_level_args = vec![];
+    let mut sub_args = vec![];
+    for (flag, value) in &flags {
+        if matches.occurrences_of(flag) > 0 || sub_matches.occurrences_of(flag) > 0 {
+            continue;
+        }
+
+        let flag_args = flag_to_args(flag, value);
+        if flag_args.is_empty() {
+            continue;
+        }
+
+        let mut trial_args = vec![program.clone()];
+        trial_args.extend(flag_args.iter().cloned());
+        if parses_at_top_level(&trial_args) {
+            top_level_args.extend(flag_args);
+        } else {
+            sub_args.extend(flag_args);
+        }
+    }
+
+    let sub_end = if sub_matches
+        .subcommand_matches(DUMP_CONFIG_SUBCOMMAND)
+        .is_some()
+    {
+        args.iter()
+            .rposition(|arg| arg == DUMP_CONFIG_SUBCOMMAND)
+            .unwrap_or_else(|| args.len())
+    } else {
+        args.len()
+    };
+    args.splice(sub_end..sub_end, sub_args);
+
+    let program_end = std::cmp::min(1, args.len());
+    args.splice(program_end..program_end, top_level_args);
+
+    Ok(Some(args))
+}
+
+/// Collects all flags which were explicitly supplied to the top-level command or its subcommand
+/// (either on the command line or via a config file). Flags with default values are omitted.
+pub fn flags_from_matches(matches: &ArgMatches) -> Flags {
+    let mut flags = Flags::new();
+
+    for level in &[matches, command_matches(matches)] {
+        // Note: `ArgMatches::args` is hidden from the `clap` docs, however it is the only way to
+        // enumerate the arguments which were matched.
+        flags.extend(
+            level
+                .args
+                .keys()
+                .filter(|flag| !DISALLOWED_FLAGS.contains(*flag))
+                .filter(|flag| level.occurrences_of(flag) > 0)
+                .map(|flag| {
+                    let value = match level.values_of(flag) {
+                        Some(values) => FlagValue::String(values.collect::<Vec<_>>().join(",")),
+                        None => FlagValue::Switch(true),
+                    };
+                    (flag.to_string(), value)
+                }),
+        );
+    }
+
+    flags
+}
@@ -1,3 +1,4 @@
+mod config_file;
 mod metrics;
 
 use beacon_node::{get_eth2_network_config, ProductionBeaconNode};
@@ -9,11 +10,12 @@ use eth2_network_config::{Eth2NetworkConfig, DEFAULT_HARDCODED_NETWORK};
 use lighthouse_version::VERSION;
 use malloc_utils::configure_memory_allocator;
 use slog::{crit, info, warn};
+use std::ffi::OsString;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use task_executor::ShutdownReason;
-use types::{EthSpec, EthSpecId};
+use types::{EthSpec, EthSpecId, YamlConfig};
 use validator_client::ProductionValidatorClient;
 
 pub const ETH2_CONFIG_FILENAME: &str = "eth2-spec.toml";
@@ -30,9 +32,22 @@ fn bls_library_name() -> &'static str {
     }
 }
 
-fn main() {
-    // Parse the CLI parameters.
-    let matches = App::new("Lighthouse")
+/// Parses `args` as the Lighthouse CLI, exiting the process if they are invalid.
+fn cli_matches<I, T>(args: I) -> ArgMatches<'static>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    try_cli_matches(args).unwrap_or_else(|e| e.exit())
+}
+
+/// Parses `args` as the Lighthouse CLI.
+fn try_cli_matches<I, T>(args: I) -> clap::Result<ArgMatches<'static>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    App::new("Lighthouse")
         .version(VERSION.replace("Lighthouse/", "").as_str())
         .author("Sigma Prime <contact@sigmaprime.io>")
         .setting(clap::AppSettings::ColoredHelp)
@@ -129,6 +144,28 @@ fn main() {
                 .global(true)
 
         )
+        .arg(
+            Arg::with_name("attestation-production-delay-ms")
+                .long("attestation-production-delay-ms")
+                .value_name("MILLISECONDS")
+                .help("Overrides the delay after the start of each slot at which unaggregated \
+                    attestations are produced. Defaults to one third of the slot, or the \
+                    ATTESTATION_PRODUCTION_DELAY_MILLIS value of the network config. Only \
+                    intended for networks with non-standard slot timings.")
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("aggregate-production-delay-ms")
+                .long("aggregate-production-delay-ms")
+                .value_name("MILLISECONDS")
+                .help("Overrides the delay after the start of each slot at which aggregate \
+                    attestations are produced. Defaults to two thirds of the slot, or the \
+                    AGGREGATE_PRODUCTION_DELAY_MILLIS value of the network config. Only \
+                    intended for networks with non-standard slot timings.")
+                .takes_value(true)
+                .global(true)
+        )
         .arg(
             Arg::with_name("dump-config")
                 .long("dump-config")
@@ -137,6 +174,26 @@ fn main() {
                 .takes_value(true)
                 .global(true)
         )
+        .arg(
+            Arg::with_name("dump-chain-config")
+                .long("dump-chain-config")
+                .hidden(true)
+                .help("Dumps the chain config to a desired location. Used for testing only.")
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name(config_file::CONFIG_FILE_FLAG)
+                .long(config_file::CONFIG_FILE_FLAG)
+                .value_name("FILE")
+                .help(
+                    "Path to a TOML (.toml) or YAML (.yml, .yaml) file containing values for CLI \
+                    flags, keyed by the long flag name (e.g., `http-port = 5052`). Flags which take \
+                    no value should be set to `true` or `false`. Values supplied on the command \
+                    line take precedence over those in the file.")
+                .takes_value(true)
+                .global(true)
+        )
         .arg(
             Arg::with_name("immediate-shutdown")
                 .long("immediate-shutdown")
@@ -156,12 +213,44 @@ fn main() {
                 )
                 .global(true),
         )
-        .subcommand(beacon_node::cli_app())
+        .subcommand(beacon_node::cli_app().subcommand(config_file::cli_app()))
         .subcommand(boot_node::cli_app())
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
         .subcommand(remote_signer::cli_app())
-        .get_matches();
+        .get_matches_from_safe(args)
+}
+
+fn main() {
+    // Parse the CLI parameters.
+    let matches = cli_matches(std::env::args_os());
+
+    // If a config file was supplied, parse the CLI parameters again with the flags from the config
+    // file added. Re-parsing ensures the config file is subject to the same validation as the
+    // command line.
+    let matches =
+        match config_file::args_with_config_file(&matches, |args| try_cli_matches(args).is_ok()) {
+            Ok(Some(args)) => cli_matches(args),
+            Ok(None) => matches,
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1)
+            }
+        };
+
+    if let Some(path) = config_file::command_matches(&matches)
+        .subcommand_matches(config_file::DUMP_CONFIG_SUBCOMMAND)
+        .and_then(|dump_matches| dump_matches.value_of("path"))
+    {
+        let flags = config_file::flags_from_matches(&matches);
+        match config_file::write_flags(Path::new(path), &flags) {
+            Ok(()) => exit(0),
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1)
+            }
+        }
+    }
 
     // Configure the allocator early in the process, before it has the chance to use the default values for
     // anything important.
@@ -267,6 +356,25 @@ fn run<E: EthSpec>(
         .optional_eth2_network_config(Some(testnet_config))?
         .build()?;
 
+    // Apply any overrides to the intra-slot timings, which are used by both the beacon node and the
+    // validator client.
+    let spec = &mut environment.eth2_config.spec;
+    if let Some(delay) = clap_utils::parse_optional(matches, "attestation-production-delay-ms")? {
+        spec.attestation_production_delay_millis = Some(delay);
+    }
+    if let Some(delay) = clap_utils::parse_optional(matches, "aggregate-production-delay-ms")? {
+        spec.aggregate_production_delay_millis = Some(delay);
+    }
+    spec.verify_attestation_production_delays()?;
+
+    if let Some(dump_path) = clap_utils::parse_optional::<PathBuf>(matches, "dump-chain-config")? {
+        let chain_config = YamlConfig::from_spec::<E>(spec);
+        let mut file = File::create(dump_path)
+            .map_err(|e| format!("Failed to create dumped chain config: {:?}", e))?;
+        serde_yaml::to_writer(&mut file, &chain_config)
+            .map_err(|e| format!("Error serializing chain config: {:?}", e))?;
+    }
+
     let log = environment.core_context().log().clone();
 
     // Allow Prometheus to export the time at which the process was started.
@@ -1,3 +1,4 @@
+use beacon_node::beacon_chain::AttestationPackingStrategy;
 use beacon_node::ClientConfig as Config;
 
 use eth2_libp2p::PeerId;
@@ -10,8 +11,9 @@ use std::path::PathBuf;
 use std::process::{Command, Output};
 use std::str::{from_utf8, FromStr};
 use std::string::ToString;
+use std::time::Duration;
 use tempfile::TempDir;
-use types::{Checkpoint, Epoch, Hash256};
+use types::{ChainSpec, Checkpoint, Epoch, Hash256, MainnetEthSpec, YamlConfig};
 
 const BEACON_CMD: &str = "beacon_node";
 const CONFIG_NAME: &str = "bn_dump.json";
@@ -176,6 +178,101 @@ fn max_skip_slots_flag() {
         .run()
         .with_config(|config| assert_eq!(config.chain.import_max_skip_slots, Some(10)));
 }
+#[test]
+fn persist_metrics_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.chain.persist_metrics));
+    CommandLineTest::new()
+        .flag("persist-metrics", None)
+        .run()
+        .with_config(|config| assert!(config.chain.persist_metrics));
+}
+#[test]
+fn disk_space_threshold_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.chain.disk_space_threshold, None));
+    CommandLineTest::new()
+        .flag("disk-space-threshold", Some("512"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.chain.disk_space_threshold, Some(512 * 1024 * 1024))
+        });
+}
+#[test]
+fn attestation_packing_strategy_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(
+            config.chain.attestation_packing_strategy,
+            AttestationPackingStrategy::Greedy
+        )
+    });
+    CommandLineTest::new()
+        .flag("attestation-packing-strategy", Some("local-search"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_strategy,
+                AttestationPackingStrategy::LocalSearch
+            )
+        });
+}
+#[test]
+fn maximum_gossip_clock_disparity_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.chain.maximum_gossip_clock_disparity_millis, None));
+    CommandLineTest::new()
+        .flag("maximum-gossip-clock-disparity", Some("1000"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.maximum_gossip_clock_disparity_millis,
+                Some(1000)
+            )
+        });
+}
+#[test]
+fn attestation_propagation_slot_range_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.chain.attestation_propagation_slot_range, None));
+    CommandLineTest::new()
+        .flag("attestation-propagation-slot-range", Some("16"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.chain.attestation_propagation_slot_range, Some(16))
+        });
+}
+#[test]
+fn attestation_production_delay_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let chain_config_path = dir.path().join("chain_config.yaml");
+    CommandLineTest::new()
+        .flag("attestation-production-delay-ms", Some("2000"))
+        .flag("aggregate-production-delay-ms", Some("6000"))
+        .flag("dump-chain-config", chain_config_path.to_str())
+        .run();
+
+    let chain_config: YamlConfig = serde_yaml::from_reader(
+        File::open(chain_config_path).expect("Unable to open dumped chain config"),
+    )
+    .expect("Unable to deserialize to YamlConfig");
+    let spec = chain_config
+        .apply_to_chain_spec::<MainnetEthSpec>(&ChainSpec::mainnet())
+        .expect("Chain config should match the mainnet spec");
+    assert_eq!(spec.attestation_production_delay_millis, Some(2000));
+    assert_eq!(spec.aggregate_production_delay_millis, Some(6000));
+}
+#[test]
+#[should_panic(expected = "must be less than the aggregate production delay")]
+fn attestation_production_delay_after_aggregate_delay_flags() {
+    CommandLineTest::new()
+        .flag("attestation-production-delay-ms", Some("8000"))
+        .flag("aggregate-production-delay-ms", Some("6000"))
+        .run();
+}
 
 #[test]
 fn freezer_dir_flag() {
@@ -294,6 +391,29 @@ fn network_target_peers_flag() {
         });
 }
 #[test]
+fn network_dynamic_target_peers_flag() {
+    CommandLineTest::new()
+        .flag("dynamic-target-peers", None)
+        .run()
+        .with_config(|config| assert!(config.network.dynamic_target_peers));
+}
+#[test]
+fn network_parent_lookup_flags() {
+    CommandLineTest::new()
+        .flag("parent-lookup-max-download-attempts", Some("10"))
+        .flag("parent-lookup-max-processing-attempts", Some("2"))
+        .flag("parent-lookup-retry-delay", Some("500"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.network.parent_lookup.max_download_attempts, 10);
+            assert_eq!(config.network.parent_lookup.max_processing_attempts, 2);
+            assert_eq!(
+                config.network.parent_lookup.retry_delay,
+                Duration::from_millis(500)
+            );
+        });
+}
+#[test]
 fn network_subscribe_all_subnets_flag() {
     CommandLineTest::new()
         .flag("subscribe-all-subnets", None)
@@ -810,6 +930,116 @@ pub fn malloc_tuning_flag() {
         });
 }
 #[test]
+fn config_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    for (name, contents) in &[
+        (
+            "config.toml",
+            "http = true\nhttp-address = \"127.0.0.99\"\n",
+        ),
+        ("config.yaml", "http: true\nhttp-address: 127.0.0.99\n"),
+    ] {
+        let path = dir.path().join(name);
+        File::create(&path)
+            .expect("Unable to create config file")
+            .write_all(contents.as_bytes())
+            .expect("Unable to write to config file");
+        CommandLineTest::new()
+            .flag("config-file", path.as_os_str().to_str())
+            .run()
+            .with_config(|config| {
+                assert!(config.http_api.enabled);
+                assert_eq!(
+                    config.http_api.listen_addr,
+                    "127.0.0.99".parse::<Ipv4Addr>().unwrap()
+                );
+            });
+    }
+}
+#[test]
+fn config_file_cli_precedence() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("config.toml");
+    File::create(&path)
+        .expect("Unable to create config file")
+        .write_all(b"http-address = \"127.0.0.99\"\n")
+        .expect("Unable to write to config file");
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .flag("http-address", Some("127.0.0.98"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.listen_addr,
+                "127.0.0.98".parse::<Ipv4Addr>().unwrap()
+            )
+        });
+}
+#[test]
+fn config_file_disallowed_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("config.toml");
+    File::create(&path)
+        .expect("Unable to create config file")
+        .write_all(b"dump-config = \"/tmp/config.json\"\n")
+        .expect("Unable to write to config file");
+    let mut cmd = base_cmd();
+    cmd.arg("--config-file").arg(path.as_os_str());
+    assert!(output_result(&mut cmd).is_err());
+}
+#[test]
+fn config_file_unknown_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("config.toml");
+    File::create(&path)
+        .expect("Unable to create config file")
+        .write_all(b"not-a-flag = true\n")
+        .expect("Unable to write to config file");
+    let mut cmd = base_cmd();
+    cmd.arg("--config-file").arg(path.as_os_str());
+    assert!(output_result(&mut cmd).is_err());
+}
+#[test]
+fn config_file_top_level_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("config.toml");
+    File::create(&path)
+        .expect("Unable to create config file")
+        .write_all(b"log-format = \"JSON\"\nhttp = true\n")
+        .expect("Unable to write to config file");
+    // `--log-format` belongs to the top-level command and is not accepted after `beacon_node`.
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .run()
+        .with_config(|config| assert!(config.http_api.enabled));
+}
+#[test]
+fn dump_config_subcommand() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    for name in &["config.toml", "config.yaml"] {
+        let path = dir.path().join(name);
+        let mut cmd = base_cmd();
+        cmd.arg("--http")
+            .arg("--http-address")
+            .arg("127.0.0.99")
+            .arg("dump-config")
+            .arg(path.as_os_str());
+        output_result(&mut cmd).expect("Unable to dump config file");
+
+        // The dumped file should produce the same config when supplied with `--config-file`.
+        CommandLineTest::new()
+            .flag("config-file", path.as_os_str().to_str())
+            .run()
+            .with_config(|config| {
+                assert!(config.http_api.enabled);
+                assert_eq!(
+                    config.http_api.listen_addr,
+                    "127.0.0.99".parse::<Ipv4Addr>().unwrap()
+                );
+            });
+    }
+}
+#[test]
 #[should_panic]
 fn ensure_panic_on_failed_launch() {
     CommandLineTest::new()
@@ -6,12 +6,12 @@ use crate::{
 };
 use environment::RuntimeContext;
 use futures::future::FutureExt;
-use slog::{crit, error, info, trace};
-use slot_clock::SlotClock;
+use slog::{crit, error, info, trace, warn};
+use slot_clock::{SlotClock, SlotEvent, SlotEventBus, SlotOffset};
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::Arc;
-use tokio::time::{sleep, sleep_until, Duration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
 use tree_hash::TreeHash;
 use types::{
     AggregateSignature, Attestation, AttestationData, BitList, ChainSpec, CommitteeIndex, EthSpec,
@@ -23,6 +23,7 @@ pub struct AttestationServiceBuilder<T, E: EthSpec> {
     duties_service: Option<Arc<DutiesService<T, E>>>,
     validator_store: Option<ValidatorStore<T, E>>,
     slot_clock: Option<T>,
+    slot_event_bus: Option<SlotEventBus>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
 }
@@ -33,6 +34,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             duties_service: None,
             validator_store: None,
             slot_clock: None,
+            slot_event_bus: None,
             beacon_nodes: None,
             context: None,
         }
@@ -53,6 +55,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn slot_event_bus(mut self, slot_event_bus: SlotEventBus) -> Self {
+        self.slot_event_bus = Some(slot_event_bus);
+        self
+    }
+
     pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
         self.beacon_nodes = Some(beacon_nodes);
         self
@@ -75,6 +82,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or("Cannot build AttestationService without slot_clock")?,
+                slot_event_bus: self
+                    .slot_event_bus
+                    .ok_or("Cannot build AttestationService without slot_event_bus")?,
                 beacon_nodes: self
                     .beacon_nodes
                     .ok_or("Cannot build AttestationService without beacon_nodes")?,
@@ -91,6 +101,7 @@ pub struct Inner<T, E: EthSpec> {
     duties_service: Arc<DutiesService<T, E>>,
     validator_store: ValidatorStore<T, E>,
     slot_clock: T,
+    slot_event_bus: SlotEventBus,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
 }
@@ -125,7 +136,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     pub fn start_update_service(self, spec: &ChainSpec) -> Result<(), String> {
         let log = self.context.log().clone();
 
-        let slot_duration = Duration::from_secs(spec.seconds_per_slot);
+        // The points in each slot at which unaggregated and aggregated attestations are produced.
+        let attestation_offset = SlotOffset::Custom(spec.unagg_attestation_production_delay());
+        let aggregation_offset = SlotOffset::Custom(spec.agg_attestation_production_delay());
         let duration_to_next_slot = self
             .slot_clock
             .duration_to_next_slot()
@@ -138,30 +151,43 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         );
 
         let executor = self.context.executor.clone();
+        let mut slot_events = self
+            .slot_event_bus
+            .subscribe(&[attestation_offset, aggregation_offset]);
 
         let interval_fut = async move {
             loop {
-                if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
-                    let log = self.context.log();
-
-                    if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
-                        crit!(
-                            log,
-                            "Failed to spawn attestation tasks";
-                            "error" => e
-                        )
-                    } else {
-                        trace!(
-                            log,
-                            "Spawned attestation tasks";
-                        )
+                match slot_events.recv().await {
+                    Ok(SlotEvent { slot, offset }) if offset == attestation_offset => {
+                        if let Err(e) = self.spawn_attestation_tasks(slot, aggregation_offset) {
+                            crit!(
+                                log,
+                                "Failed to spawn attestation tasks";
+                                "error" => e
+                            )
+                        } else {
+                            trace!(
+                                log,
+                                "Spawned attestation tasks";
+                            )
+                        }
+                    }
+                    // Prune the slashing protection database once all unaggregated attestations
+                    // have (hopefully) been signed, i.e. at the same time as aggregate production.
+                    Ok(SlotEvent { slot, offset }) if offset == aggregation_offset => {
+                        self.spawn_slashing_protection_pruning_task(slot)
+                    }
+                    // Ignore events for offsets registered by other services.
+                    Ok(_) => (),
+                    Err(RecvError::Lagged(missed)) => warn!(
+                        log,
+                        "Attestation service missed slot events";
+                        "missed" => missed
+                    ),
+                    Err(RecvError::Closed) => {
+                        error!(log, "Slot event bus closed");
+                        break;
                     }
-                } else {
-                    error!(log, "Failed to read slot clock");
-                    // If we can't read the slot clock, just wait another slot.
-                    sleep(slot_duration).await;
-                    continue;
                 }
             }
         };
@@ -172,20 +198,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
     /// For each each required attestation, spawn a new task that downloads, signs and uploads the
     /// attestation to the beacon node.
-    fn spawn_attestation_tasks(&self, slot_duration: Duration) -> Result<(), String> {
-        let slot = self.slot_clock.now().ok_or("Failed to read slot clock")?;
-        let duration_to_next_slot = self
-            .slot_clock
-            .duration_to_next_slot()
-            .ok_or("Unable to determine duration to next slot")?;
-
-        // If a validator needs to publish an aggregate attestation, they must do so at 2/3
-        // through the slot. This delay triggers at this time
-        let aggregate_production_instant = Instant::now()
-            + duration_to_next_slot
-                .checked_sub(slot_duration / 3)
-                .unwrap_or_else(|| Duration::from_secs(0));
-
+    fn spawn_attestation_tasks(
+        &self,
+        slot: Slot,
+        aggregation_offset: SlotOffset,
+    ) -> Result<(), String> {
         let duties_by_committee_index: HashMap<CommitteeIndex, Vec<DutyAndProof>> = self
             .duties_service
             .attesters(slot)
@@ -204,6 +221,10 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         duties_by_committee_index
             .into_iter()
             .for_each(|(committee_index, validator_duties)| {
+                // Subscribe before the aggregation offset of `slot` is reached, so that the event
+                // is not missed if producing the attestations takes longer than expected.
+                let aggregation_events = self.slot_event_bus.subscribe(&[aggregation_offset]);
+
                 // Spawn a separate task for each attestation.
                 self.inner.context.executor.spawn(
                     self.clone()
@@ -211,18 +232,14 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                             slot,
                             committee_index,
                             validator_duties,
-                            aggregate_production_instant,
+                            aggregation_events,
+                            aggregation_offset,
                         )
                         .map(|_| ()),
                     "attestation publish",
                 );
             });
 
-        // Schedule pruning of the slashing protection database once all unaggregated
-        // attestations have (hopefully) been signed, i.e. at the same time as aggregate
-        // production.
-        self.spawn_slashing_protection_pruning_task(slot, aggregate_production_instant);
-
         Ok(())
     }
 
@@ -240,7 +257,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         slot: Slot,
         committee_index: CommitteeIndex,
         validator_duties: Vec<DutyAndProof>,
-        aggregate_production_instant: Instant,
+        mut aggregation_events: broadcast::Receiver<SlotEvent>,
+        aggregation_offset: SlotOffset,
     ) -> Result<(), ()> {
         let log = self.context.log();
         let attestations_timer = metrics::start_timer_vec(
@@ -276,11 +294,20 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         //
         // If an attestation was produced, make an aggregate.
         if let Some(attestation_data) = attestation_opt {
-            // First, wait until the `aggregation_production_instant` (2/3rds
-            // of the way though the slot). As verified in the
-            // `delay_triggers_when_in_the_past` test, this code will still run
-            // even if the instant has already elapsed.
-            sleep_until(aggregate_production_instant).await;
+            // First, wait until the slot event bus reaches the aggregation offset (2/3rds of the
+            // way through the slot by default). Events are buffered by the receiver, so this
+            // returns immediately if the offset has already passed.
+            wait_for_slot_event(&mut aggregation_events, slot, aggregation_offset)
+                .await
+                .map_err(|e| {
+                    error!(
+                        log,
+                        "Unable to wait for aggregation";
+                        "error" => ?e,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    )
+                })?;
 
             // Start the metrics timer *after* we've done the delay.
             let _aggregates_timer = metrics::start_timer_vec(
@@ -548,36 +575,49 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
     /// Spawn a blocking task to run the slashing protection pruning process.
     ///
-    /// Start the task at `pruning_instant` to avoid interference with other tasks.
-    fn spawn_slashing_protection_pruning_task(&self, slot: Slot, pruning_instant: Instant) {
+    /// This is called at the aggregation offset of `slot` to avoid interference with other tasks.
+    fn spawn_slashing_protection_pruning_task(&self, slot: Slot) {
         let attestation_service = self.clone();
-        let executor = self.inner.context.executor.clone();
         let current_epoch = slot.epoch(E::slots_per_epoch());
 
-        // Wait for `pruning_instant` in a regular task, and then switch to a blocking one.
-        self.inner.context.executor.spawn(
-            async move {
-                sleep_until(pruning_instant).await;
-
-                executor.spawn_blocking(
-                    move || {
-                        attestation_service
-                            .validator_store
-                            .prune_slashing_protection_db(current_epoch, false)
-                    },
-                    "slashing_protection_pruning",
-                )
+        self.inner.context.executor.spawn_blocking(
+            move || {
+                attestation_service
+                    .validator_store
+                    .prune_slashing_protection_db(current_epoch, false)
             },
-            "slashing_protection_pre_pruning",
+            "slashing_protection_pruning",
         );
     }
 }
 
+/// Waits until `events` yields `offset` for `slot`, or any event for a later slot.
+///
+/// If events were missed because the receiver lagged, the offset may already have passed, so this
+/// returns rather than waiting for the next slot.
+async fn wait_for_slot_event(
+    events: &mut broadcast::Receiver<SlotEvent>,
+    slot: Slot,
+    offset: SlotOffset,
+) -> Result<(), RecvError> {
+    loop {
+        match events.recv().await {
+            Ok(event) if event.slot > slot || (event.slot == slot && event.offset == offset) => {
+                return Ok(())
+            }
+            Ok(_) => (),
+            Err(RecvError::Lagged(_)) => return Ok(()),
+            Err(e @ RecvError::Closed) => return Err(e),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use futures::future::FutureExt;
     use parking_lot::RwLock;
+    use tokio::time::{sleep_until, Duration, Instant};
 
     /// This test is to ensure that a `tokio_timer::Sleep` with an instant in the past will still
     /// trigger.
@@ -1,7 +1,8 @@
 //! The `DutiesService` contains the attester/proposer duties for all local validators.
 //!
 //! It learns of the local validator via the `crate::ValidatorStore` struct. It keeps the duties
-//! up-to-date by polling the beacon node on regular intervals.
+//! up-to-date by polling the beacon node on regular intervals. It also subscribes to `dependent_root`
+//! events from the beacon node so that attester duties can be updated as soon as a re-org occurs.
 //!
 //! The `DutiesService` is also responsible for sending events to the `BlockService` which trigger
 //! block production.
@@ -11,7 +12,12 @@ use crate::{
     block_service::BlockServiceNotification, http_metrics::metrics, validator_store::ValidatorStore,
 };
 use environment::RuntimeContext;
-use eth2::types::{AttesterData, BeaconCommitteeSubscription, ProposerData, StateId, ValidatorId};
+use eth2::reqwest;
+use eth2::types::{
+    AttesterData, BeaconCommitteeSubscription, EventKind, EventTopic, ProposerData, StateId,
+    ValidatorId,
+};
+use futures::StreamExt;
 use parking_lot::RwLock;
 use safe_arith::ArithError;
 use slog::{debug, error, info, warn, Logger};
@@ -102,6 +108,9 @@ pub struct DutiesService<T, E: EthSpec> {
     pub slot_clock: T,
     /// Provides HTTP access to remote beacon nodes.
     pub beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    /// An HTTP client without a total request timeout, used for streaming events from the beacon
+    /// nodes.
+    pub event_stream_client: reqwest::Client,
     /// Controls whether or not this function will refuse to interact with non-synced beacon nodes.
     ///
     /// This functionality is a little redundant since most BNs will likely reject duties when they
@@ -265,6 +274,89 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
         },
         "duties_service_attesters",
     );
+
+    /*
+     * Spawn the task which listens for re-orgs that change the dependent roots of our duties.
+     */
+    let duties_service = core_duties_service.clone();
+    core_duties_service.context.executor.spawn(
+        async move { listen_for_dependent_root_events(&duties_service).await },
+        "duties_service_dependent_roots",
+    );
+}
+
+/// Subscribe to `dependent_root` events on the beacon node, re-downloading attester duties (and
+/// re-sending the committee subscriptions) each time a re-org changes the dependent roots.
+///
+/// Proposer duties are not updated here since they are always polled at the start of the slot,
+/// before any block is produced. Polling them here would send duplicate notifications to the
+/// `BlockService`.
+async fn listen_for_dependent_root_events<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+) {
+    let log = duties_service.context.log();
+    let event_stream_client = &duties_service.event_stream_client;
+
+    loop {
+        let subscribe_result = duties_service
+            .beacon_nodes
+            .first_success(duties_service.require_synced, |beacon_node| async move {
+                beacon_node
+                    .with_client(event_stream_client.clone())
+                    .get_events::<E>(&[EventTopic::DependentRoot])
+                    .await
+            })
+            .await;
+
+        match subscribe_result {
+            Ok(mut events) => {
+                // The stream ends when the connection to the beacon node is lost.
+                while let Some(event) = events.next().await {
+                    match event {
+                        Ok(EventKind::DependentRoot(event)) => {
+                            info!(
+                                log,
+                                "Dependent root changed, updating duties";
+                                "current_duty_dependent_root" => %event.current_duty_dependent_root,
+                                "previous_duty_dependent_root" => %event.previous_duty_dependent_root,
+                                "head_block" => %event.block,
+                                "head_slot" => event.slot,
+                            );
+
+                            if let Err(e) = poll_beacon_attesters(duties_service).await {
+                                error!(
+                                    log,
+                                    "Failed to poll beacon attesters";
+                                    "error" => ?e
+                                );
+                            }
+                        }
+                        Ok(_) => (),
+                        // Keep-alive messages are not valid events, ignore them.
+                        Err(e) => debug!(
+                            log,
+                            "Unable to parse dependent root event";
+                            "error" => ?e
+                        ),
+                    }
+                }
+
+                // Re-subscribe straight away so that no re-orgs are missed.
+                debug!(log, "Dependent root event stream ended, re-subscribing");
+            }
+            Err(e) => {
+                debug!(
+                    log,
+                    "Unable to subscribe to dependent root events";
+                    "error" => %e
+                );
+
+                // Wait a slot before re-subscribing, the beacon node may be offline or may not
+                // support the `dependent_root` topic.
+                sleep(duties_service.slot_clock.slot_duration()).await;
+            }
+        }
+    }
 }
 
 /// Iterate through all the voting pubkeys in the `ValidatorStore` and attempt to learn any unknown
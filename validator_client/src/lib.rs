@@ -30,7 +30,14 @@ use clap::ArgMatches;
 use duties_service::DutiesService;
 use environment::RuntimeContext;
 use eth2::types::StateId;
-use eth2::{reqwest::ClientBuilder, BeaconNodeHttpClient, StatusCode};
+use eth2::types::VALIDATOR_CLIENT_INSTANCE_HEADER;
+use eth2::{
+    reqwest::{
+        header::{HeaderMap, HeaderValue},
+        ClientBuilder,
+    },
+    BeaconNodeHttpClient, StatusCode,
+};
 use fork_service::{ForkService, ForkServiceBuilder};
 use http_api::ApiSecret;
 use initialized_validators::InitializedValidators;
@@ -39,7 +46,7 @@ use parking_lot::RwLock;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 use slog::{error, info, warn, Logger};
 use slot_clock::SlotClock;
-use slot_clock::SystemTimeSlotClock;
+use slot_clock::{SlotEventBus, SystemTimeSlotClock};
 use std::marker::PhantomData;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -68,6 +75,8 @@ pub struct ProductionValidatorClient<T: EthSpec> {
     block_service: BlockService<SystemTimeSlotClock, T>,
     attestation_service: AttestationService<SystemTimeSlotClock, T>,
     validator_store: ValidatorStore<SystemTimeSlotClock, T>,
+    slot_clock: SystemTimeSlotClock,
+    slot_event_bus: SlotEventBus,
     http_api_listen_addr: Option<SocketAddr>,
     http_metrics_ctx: Option<Arc<http_metrics::Context<T>>>,
     config: Config,
@@ -222,6 +231,21 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 })?;
         }
 
+        // A random identifier which is sent to all beacon nodes, allowing them to detect the same
+        // validators being run in more than one validator client.
+        let instance_id = format!("{:016x}", rand::random::<u64>());
+        info!(
+            log,
+            "Generated validator client instance identifier";
+            "instance_id" => &instance_id
+        );
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert(
+            VALIDATOR_CLIENT_INSTANCE_HEADER,
+            HeaderValue::from_str(&instance_id)
+                .map_err(|e| format!("Invalid instance identifier: {:?}", e))?,
+        );
+
         let beacon_nodes: Vec<BeaconNodeHttpClient> = config
             .beacon_nodes
             .clone()
@@ -229,6 +253,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .map(|url| {
                 let beacon_node_http_client = ClientBuilder::new()
                     .timeout(HTTP_TIMEOUT)
+                    .default_headers(default_headers.clone())
                     .build()
                     .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
                 Ok(BeaconNodeHttpClient::from_components(
@@ -238,6 +263,14 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             })
             .collect::<Result<Vec<BeaconNodeHttpClient>, String>>()?;
 
+        // Event streams are long-lived, so they must not be subject to the total request timeout
+        // of the clients above. Only the connection attempt is limited.
+        let event_stream_client = ClientBuilder::new()
+            .connect_timeout(HTTP_TIMEOUT)
+            .default_headers(default_headers)
+            .build()
+            .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+
         let num_nodes = beacon_nodes.len();
         let candidates = beacon_nodes
             .into_iter()
@@ -311,6 +344,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             indices: <_>::default(),
             slot_clock: slot_clock.clone(),
             beacon_nodes: beacon_nodes.clone(),
+            event_stream_client,
             validator_store: validator_store.clone(),
             require_synced: if config.allow_unsynced_beacon_node {
                 RequireSynced::Yes
@@ -336,9 +370,12 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .graffiti_file(config.graffiti_file.clone())
             .build()?;
 
+        let slot_event_bus = SlotEventBus::new();
+
         let attestation_service = AttestationServiceBuilder::new()
             .duties_service(duties_service.clone())
-            .slot_clock(slot_clock)
+            .slot_clock(slot_clock.clone())
+            .slot_event_bus(slot_event_bus.clone())
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
@@ -357,6 +394,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             block_service,
             attestation_service,
             validator_store,
+            slot_clock,
+            slot_event_bus,
             config,
             http_api_listen_addr: None,
             http_metrics_ctx,
@@ -371,6 +410,11 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let (block_service_tx, block_service_rx) = mpsc::channel(channel_capacity);
         let log = self.context.log();
 
+        self.context.executor.spawn(
+            self.slot_event_bus.clone().run(self.slot_clock.clone()),
+            "slot_event_bus",
+        );
+
         duties_service::start_update_service(self.duties_service.clone(), block_service_tx);
 
         self.fork_service